@@ -0,0 +1,387 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{ensure, Addr, Api, Binary, Env, Uint128};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::Campaign;
+
+/// Domain of the attestation sign bytes, so signatures can't be replayed from other messages.
+pub const ATTESTATION_DOMAIN: &str = "mantra-claimdrop-attestation";
+
+/// The signer authorized to attest allocations off-chain for a campaign.
+#[cw_serde]
+pub enum Attester {
+    /// A secp256k1 public key, in compressed (33 bytes) or uncompressed (65 bytes) form
+    Secp256k1 { pubkey: Binary },
+    /// An ed25519 public key (32 bytes)
+    Ed25519 { pubkey: Binary },
+}
+
+/// An allocation attested off-chain by the campaign's [Attester], submitted with the claim.
+///
+/// Replay protection: `allocation` is the total amount attested for the address, not the amount
+/// to claim, so the same attestation can be submitted on every claim while the allocation vests,
+/// and claims can never exceed it. The contract stores the highest nonce used by each address and
+/// rejects attestations with a lower nonce, or with a nonce lower than the campaign's
+/// `min_attestation_nonce`.
+///
+/// Since the claimer picks which attestation to submit, a new attestation with a higher nonce only
+/// supersedes an older one if it's more favorable, i.e. increases the allocation. To reduce or
+/// revoke allocations, the attester signs new attestations with a nonce at or above a new minimum,
+/// and the campaign owner raises `min_attestation_nonce` to it with
+/// [ExecuteMsg::RaiseMinAttestationNonce](crate::msg::ExecuteMsg::RaiseMinAttestationNonce),
+/// invalidating every older attestation.
+#[cw_serde]
+pub struct Attestation {
+    /// The total amount allocated to the address
+    pub allocation: Uint128,
+    /// The nonce of the attestation
+    pub nonce: u64,
+    /// The signature of the [attestation_sign_bytes] by the attester. A 64 bytes `r || s`
+    /// signature for secp256k1, or a 64 bytes signature for ed25519.
+    pub signature: Binary,
+}
+
+impl Attester {
+    /// Validates the public key length
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let valid = match self {
+            Attester::Secp256k1 { pubkey } => pubkey.len() == 33 || pubkey.len() == 65,
+            Attester::Ed25519 { pubkey } => pubkey.len() == 32,
+        };
+
+        ensure!(
+            valid,
+            ContractError::InvalidCampaignParam {
+                param: "attester".to_string(),
+                reason: "invalid public key length".to_string(),
+            }
+        );
+
+        Ok(())
+    }
+}
+
+/// Gets the canonical bytes the attester signs for an allocation, i.e.
+/// `{ATTESTATION_DOMAIN}:{chain_id}:{campaign}:{address}:{amount}:{nonce}`, where `campaign` is
+/// the address of the claimdrop contract. The chain id keeps signatures from being replayed on
+/// another chain where the campaign got the same address.
+pub fn attestation_sign_bytes(
+    chain_id: &str,
+    campaign: &Addr,
+    address: &Addr,
+    amount: Uint128,
+    nonce: u64,
+) -> Vec<u8> {
+    format!("{ATTESTATION_DOMAIN}:{chain_id}:{campaign}:{address}:{amount}:{nonce}").into_bytes()
+}
+
+/// Verifies the attestation was signed by the campaign's attester for the current chain, the
+/// current contract as campaign, and the given address, and that its nonce is neither lower than
+/// the campaign's minimum attestation nonce nor than the last nonce used by the address.
+/// Secp256k1 signatures are verified against the sha256 hash of the sign bytes, ed25519 ones
+/// against the sign bytes.
+pub fn verify_attestation(
+    api: &dyn Api,
+    env: &Env,
+    campaign: &Campaign,
+    address: &Addr,
+    attestation: &Attestation,
+    last_nonce: Option<u64>,
+) -> Result<(), ContractError> {
+    let attester = campaign
+        .attester
+        .as_ref()
+        .ok_or_else(|| ContractError::CampaignError {
+            reason: "the campaign has no attester".to_string(),
+        })?;
+
+    ensure!(
+        attestation.nonce >= campaign.min_attestation_nonce,
+        ContractError::InvalidAttestation {
+            reason: format!(
+                "nonce {} is lower than the minimum nonce {}",
+                attestation.nonce, campaign.min_attestation_nonce
+            ),
+        }
+    );
+    ensure!(
+        last_nonce.is_none_or(|last_nonce| attestation.nonce >= last_nonce),
+        ContractError::InvalidAttestation {
+            reason: format!(
+                "nonce {} is lower than the last nonce used",
+                attestation.nonce
+            ),
+        }
+    );
+
+    let sign_bytes = attestation_sign_bytes(
+        &env.block.chain_id,
+        &env.contract.address,
+        address,
+        attestation.allocation,
+        attestation.nonce,
+    );
+
+    let verification = match attester {
+        Attester::Secp256k1 { pubkey } => api.secp256k1_verify(
+            Sha256::digest(&sign_bytes).as_slice(),
+            &attestation.signature,
+            pubkey,
+        ),
+        Attester::Ed25519 { pubkey } => {
+            api.ed25519_verify(&sign_bytes, &attestation.signature, pubkey)
+        }
+    };
+
+    ensure!(
+        verification.map_err(|e| ContractError::InvalidAttestation {
+            reason: e.to_string(),
+        })?,
+        ContractError::InvalidAttestation {
+            reason: "signature verification failed".to_string(),
+        }
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_env, MockApi};
+    use cosmwasm_std::{Addr, Binary, Env, Uint128};
+    use k256::ecdsa::signature::hazmat::PrehashSigner;
+    use k256::ecdsa::{Signature, SigningKey};
+    use sha2::{Digest, Sha256};
+
+    use crate::attestation::{attestation_sign_bytes, verify_attestation, Attestation, Attester};
+    use crate::error::ContractError;
+    use crate::msg::Campaign;
+    use crate::testing;
+
+    fn campaign(attester: Option<Attester>) -> Campaign {
+        Campaign {
+            attester,
+            ..testing::campaign(vec![])
+        }
+    }
+
+    fn attest(
+        key: &SigningKey,
+        env: &Env,
+        address: &Addr,
+        amount: u128,
+        nonce: u64,
+    ) -> Attestation {
+        let sign_bytes = attestation_sign_bytes(
+            &env.block.chain_id,
+            &env.contract.address,
+            address,
+            Uint128::new(amount),
+            nonce,
+        );
+        let signature: Signature = key.sign_prehash(&Sha256::digest(sign_bytes)).unwrap();
+
+        Attestation {
+            allocation: Uint128::new(amount),
+            nonce,
+            signature: Binary::from(signature.to_bytes().as_slice()),
+        }
+    }
+
+    #[test]
+    fn verify_secp256k1_attestation() {
+        let api = MockApi::default();
+        let env = mock_env();
+        let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let attester = Attester::Secp256k1 {
+            pubkey: Binary::from(key.verifying_key().to_encoded_point(true).as_bytes()),
+        };
+        attester.validate().unwrap();
+        let campaign = campaign(Some(attester));
+
+        let alice = Addr::unchecked("alice");
+        let attestation = attest(&key, &env, &alice, 1_000, 2);
+
+        verify_attestation(&api, &env, &campaign, &alice, &attestation, None).unwrap();
+        // the same attestation can be submitted again
+        verify_attestation(&api, &env, &campaign, &alice, &attestation, Some(2)).unwrap();
+
+        assert!(matches!(
+            verify_attestation(&api, &env, &campaign, &alice, &attestation, Some(3)),
+            Err(ContractError::InvalidAttestation { .. })
+        ));
+        assert!(matches!(
+            verify_attestation(
+                &api,
+                &env,
+                &campaign,
+                &Addr::unchecked("bob"),
+                &attestation,
+                None
+            ),
+            Err(ContractError::InvalidAttestation { .. })
+        ));
+
+        let inflated = Attestation {
+            allocation: Uint128::new(1_001),
+            ..attestation.clone()
+        };
+        assert!(matches!(
+            verify_attestation(&api, &env, &campaign, &alice, &inflated, None),
+            Err(ContractError::InvalidAttestation { .. })
+        ));
+
+        // the signature doesn't verify on another chain
+        let mut other_chain_env = mock_env();
+        other_chain_env.block.chain_id = "mantra-1".to_string();
+        assert!(matches!(
+            verify_attestation(
+                &api,
+                &other_chain_env,
+                &campaign,
+                &alice,
+                &attestation,
+                None
+            ),
+            Err(ContractError::InvalidAttestation { .. })
+        ));
+
+        assert!(Attester::Ed25519 {
+            pubkey: Binary::from([1u8; 33])
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn verify_ed25519_attestation() {
+        let api = MockApi::default();
+        let env = mock_env();
+        let key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let attester = Attester::Ed25519 {
+            pubkey: Binary::from(<[u8; 32]>::from(ed25519_zebra::VerificationKey::from(&key))),
+        };
+        attester.validate().unwrap();
+        let campaign = campaign(Some(attester));
+
+        let alice = Addr::unchecked("alice");
+        let sign_bytes = attestation_sign_bytes(
+            &env.block.chain_id,
+            &env.contract.address,
+            &alice,
+            Uint128::new(1_000),
+            1,
+        );
+        let attestation = Attestation {
+            allocation: Uint128::new(1_000),
+            nonce: 1,
+            signature: Binary::from(key.sign(&sign_bytes).to_bytes()),
+        };
+
+        verify_attestation(&api, &env, &campaign, &alice, &attestation, None).unwrap();
+
+        let inflated = Attestation {
+            allocation: Uint128::new(1_001),
+            ..attestation.clone()
+        };
+        assert!(matches!(
+            verify_attestation(&api, &env, &campaign, &alice, &inflated, None),
+            Err(ContractError::InvalidAttestation { .. })
+        ));
+
+        let mut other_chain_env = mock_env();
+        other_chain_env.block.chain_id = "mantra-1".to_string();
+        assert!(matches!(
+            verify_attestation(
+                &api,
+                &other_chain_env,
+                &campaign,
+                &alice,
+                &attestation,
+                None
+            ),
+            Err(ContractError::InvalidAttestation { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_signature_length() {
+        let api = MockApi::default();
+        let env = mock_env();
+        let alice = Addr::unchecked("alice");
+
+        let secp256k1_key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let secp256k1_campaign = campaign(Some(Attester::Secp256k1 {
+            pubkey: Binary::from(
+                secp256k1_key
+                    .verifying_key()
+                    .to_encoded_point(true)
+                    .as_bytes(),
+            ),
+        }));
+        let ed25519_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let ed25519_campaign = campaign(Some(Attester::Ed25519 {
+            pubkey: Binary::from(<[u8; 32]>::from(ed25519_zebra::VerificationKey::from(
+                &ed25519_key,
+            ))),
+        }));
+
+        let mut attestation = attest(&secp256k1_key, &env, &alice, 1_000, 1);
+        for signature in [vec![1u8; 63], vec![1u8; 65], vec![]] {
+            attestation.signature = Binary::from(signature);
+            for campaign in [&secp256k1_campaign, &ed25519_campaign] {
+                assert!(matches!(
+                    verify_attestation(&api, &env, campaign, &alice, &attestation, None),
+                    Err(ContractError::InvalidAttestation { .. })
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn min_attestation_nonce_revokes_older_attestations() {
+        let api = MockApi::default();
+        let env = mock_env();
+        let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let mut campaign = campaign(Some(Attester::Secp256k1 {
+            pubkey: Binary::from(key.verifying_key().to_encoded_point(true).as_bytes()),
+        }));
+
+        let alice = Addr::unchecked("alice");
+        let larger_attestation = attest(&key, &env, &alice, 1_000, 1);
+        let reduced_attestation = attest(&key, &env, &alice, 500, 5);
+
+        // the claimer could keep submitting the larger allocation
+        verify_attestation(&api, &env, &campaign, &alice, &larger_attestation, Some(1)).unwrap();
+
+        campaign.raise_min_attestation_nonce(5).unwrap();
+        assert!(matches!(
+            verify_attestation(&api, &env, &campaign, &alice, &larger_attestation, Some(1)),
+            Err(ContractError::InvalidAttestation { .. })
+        ));
+        verify_attestation(&api, &env, &campaign, &alice, &reduced_attestation, Some(1)).unwrap();
+
+        assert!(matches!(
+            campaign.raise_min_attestation_nonce(5),
+            Err(ContractError::InvalidInput { .. })
+        ));
+
+        let mut no_attester_campaign = self::campaign(None);
+        assert!(matches!(
+            no_attester_campaign.raise_min_attestation_nonce(1),
+            Err(ContractError::CampaignError { .. })
+        ));
+        assert!(matches!(
+            verify_attestation(
+                &api,
+                &env,
+                &no_attester_campaign,
+                &alice,
+                &larger_attestation,
+                None
+            ),
+            Err(ContractError::CampaignError { .. })
+        ));
+    }
+}
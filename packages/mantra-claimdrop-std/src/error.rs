@@ -1,4 +1,6 @@
-use cosmwasm_std::{ConversionOverflowError, Decimal, OverflowError, StdError};
+use cosmwasm_std::{
+    CheckedMultiplyRatioError, ConversionOverflowError, Decimal, OverflowError, StdError,
+};
 use cw_migrate_error_derive::cw_migrate_invalid_version_error;
 use cw_ownable::OwnershipError;
 use cw_utils::PaymentError;
@@ -22,6 +24,9 @@ pub enum ContractError {
     #[error("{0}")]
     ConversionOverflowError(#[from] ConversionOverflowError),
 
+    #[error("{0}")]
+    CheckedMultiplyRatioError(#[from] CheckedMultiplyRatioError),
+
     #[error("{0}")]
     PaymentError(#[from] PaymentError),
 
@@ -76,6 +81,12 @@ pub enum ContractError {
     #[error("Batch size limit exceeded: {actual}, maximum allowed: {max}")]
     BatchSizeLimitExceeded { actual: usize, max: usize },
 
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[error("Invalid attestation: {reason}")]
+    InvalidAttestation { reason: String },
+
     #[error("Unauthorized")]
     Unauthorized,
 }
@@ -0,0 +1,216 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{ensure, to_json_binary, Addr, Coin, WasmMsg};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+
+use crate::error::ContractError;
+use crate::msg::{CampaignAction, CampaignParams, InstantiateMsg as ClaimdropInstantiateMsg};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Owner of the contract. If not set, it is the sender of the Instantiate message.
+    pub owner: Option<String>,
+    /// The code id of the claimdrop contract the campaigns are instantiated with
+    pub claimdrop_code_id: u64,
+}
+
+#[cw_ownable_execute]
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Instantiates a claimdrop contract with a new campaign, owned by the sender. The funds sent
+    /// along are forwarded to the new contract, and the response data is a
+    /// [CreateCampaignResponse].
+    CreateCampaign {
+        /// The parameters to create the campaign with
+        params: Box<CampaignParams>,
+    },
+    /// Updates the factory configuration. Only the owner can update it.
+    UpdateConfig {
+        /// The code id of the claimdrop contract new campaigns are instantiated with
+        claimdrop_code_id: Option<u64>,
+    },
+}
+
+#[cw_ownable_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    /// Get the factory configuration
+    Config {},
+    #[returns(CampaignInfo)]
+    /// Get a campaign created by the factory
+    Campaign {
+        /// The address of the campaign's claimdrop contract
+        address: String,
+    },
+    #[returns(CampaignsResponse)]
+    /// Get the campaigns created by the factory, sorted by contract address
+    Campaigns {
+        /// If provided, only the campaigns matching the filter are returned
+        filter_by: Option<CampaignFilter>,
+        /// The contract address to start querying from. Used for paginating results.
+        start_after: Option<String>,
+        /// The maximum number of items to return. If not set, the default value is used. Used for paginating results.
+        limit: Option<u16>,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Configuration of the claimdrop factory
+#[cw_serde]
+pub struct Config {
+    /// The code id of the claimdrop contract the campaigns are instantiated with
+    pub claimdrop_code_id: u64,
+}
+
+/// The filter to apply on the Campaigns query
+#[cw_serde]
+pub enum CampaignFilter {
+    /// Campaigns created by the given address
+    Creator(String),
+    /// Campaigns rewarding the given denom
+    RewardDenom(String),
+}
+
+/// A campaign created by the factory.
+#[cw_serde]
+pub struct CampaignInfo {
+    /// The address of the campaign's claimdrop contract
+    pub address: Addr,
+    /// The address that created the campaign, and owns its claimdrop contract
+    pub creator: Addr,
+    /// The campaign name
+    pub name: String,
+    /// The denom of the campaign reward
+    pub reward_denom: String,
+    /// The unix timestamp when the campaign was created, in seconds
+    pub created_at: u64,
+}
+
+/// Response data of the CreateCampaign message.
+#[cw_serde]
+pub struct CreateCampaignResponse {
+    /// The address of the instantiated claimdrop contract
+    pub campaign_address: Addr,
+}
+
+/// Response to the Campaigns query.
+#[cw_serde]
+pub struct CampaignsResponse {
+    /// The campaigns
+    pub campaigns: Vec<CampaignInfo>,
+}
+
+/// Creates the message instantiating a claimdrop contract with the given campaign, owned by the
+/// creator and funded with the funds sent to the factory. The factory is the contract admin, so
+/// it can migrate the campaigns it created.
+pub fn create_campaign_instantiate_msg(
+    config: &Config,
+    factory: &Addr,
+    creator: &Addr,
+    params: Box<CampaignParams>,
+    funds: Vec<Coin>,
+) -> Result<WasmMsg, ContractError> {
+    ensure!(
+        !params.contract_label.is_empty(),
+        ContractError::InvalidCampaignParam {
+            param: "contract_label".to_string(),
+            reason: "cannot be empty".to_string(),
+        }
+    );
+
+    Ok(WasmMsg::Instantiate {
+        admin: Some(factory.to_string()),
+        code_id: config.claimdrop_code_id,
+        label: params.contract_label.clone(),
+        msg: to_json_binary(&ClaimdropInstantiateMsg {
+            owner: Some(creator.to_string()),
+            action: Some(CampaignAction::CreateCampaign { params }),
+        })?,
+        funds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, from_json, Addr, Decimal, WasmMsg};
+
+    use crate::error::ContractError;
+    use crate::factory::{create_campaign_instantiate_msg, Config};
+    use crate::msg::{
+        CampaignAction, CampaignParams, DistributionType, InstantiateMsg as ClaimdropInstantiateMsg,
+    };
+    use crate::testing;
+
+    fn params(contract_label: &str) -> Box<CampaignParams> {
+        Box::new(CampaignParams {
+            contract_label: contract_label.to_string(),
+            ..testing::params(vec![DistributionType::LumpSum {
+                percentage: Decimal::percent(100),
+                start_time: 1_000,
+            }])
+        })
+    }
+
+    #[test]
+    fn campaign_instantiate_msg() {
+        let config = Config {
+            claimdrop_code_id: 7,
+        };
+        let factory = Addr::unchecked("factory");
+        let creator = Addr::unchecked("creator");
+
+        let WasmMsg::Instantiate {
+            admin,
+            code_id,
+            msg,
+            funds,
+            label,
+        } = create_campaign_instantiate_msg(
+            &config,
+            &factory,
+            &creator,
+            params("airdrop"),
+            coins(1_000_000, "uom"),
+        )
+        .unwrap()
+        else {
+            panic!("expected an instantiate msg");
+        };
+
+        assert_eq!(admin, Some("factory".to_string()));
+        assert_eq!(code_id, 7);
+        assert_eq!(label, "airdrop");
+        assert_eq!(funds, coins(1_000_000, "uom"));
+        assert_eq!(
+            from_json::<ClaimdropInstantiateMsg>(&msg).unwrap(),
+            ClaimdropInstantiateMsg {
+                owner: Some("creator".to_string()),
+                action: Some(CampaignAction::CreateCampaign {
+                    params: params("airdrop"),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn empty_contract_label() {
+        let err = create_campaign_instantiate_msg(
+            &Config {
+                claimdrop_code_id: 7,
+            },
+            &Addr::unchecked("factory"),
+            &Addr::unchecked("creator"),
+            params(""),
+            vec![],
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ContractError::InvalidCampaignParam { param, .. } if param == "contract_label"
+        ));
+    }
+}
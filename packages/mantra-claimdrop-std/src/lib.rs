@@ -1,2 +1,9 @@
+pub mod attestation;
 pub mod error;
+pub mod factory;
+pub mod merkle;
 pub mod msg;
+pub mod vesting;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{ensure, from_json, HexBinary, Uint128};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+
+/// Prefix of the leaf hashes, so a leaf can't be passed off as an inner node.
+const LEAF_PREFIX: u8 = 0x00;
+/// Prefix of the inner node hashes.
+const NODE_PREFIX: u8 = 0x01;
+
+/// A 32 bytes sha256 hash.
+pub type Hash = [u8; 32];
+
+/// Hashes an allocation into a leaf of the tree, i.e. `sha256(0x00 || address || "," || amount)`,
+/// where the amount is in its decimal string representation.
+pub fn hash_leaf(address: &str, amount: Uint128) -> Hash {
+    Sha256::new()
+        .chain_update([LEAF_PREFIX])
+        .chain_update(address.as_bytes())
+        .chain_update(b",")
+        .chain_update(amount.to_string().as_bytes())
+        .finalize()
+        .into()
+}
+
+/// Hashes two nodes into their parent, i.e. `sha256(0x01 || min(a, b) || max(a, b))`. The nodes
+/// are sorted so proofs don't need to carry the side of each sibling.
+pub fn hash_pair(a: &Hash, b: &Hash) -> Hash {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    Sha256::new()
+        .chain_update([NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// A Merkle tree built from a list of allocations, used off-chain to get the campaign root and
+/// the proof of each address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// The allocations the tree was built from, in the same order as the leaves.
+    allocations: Vec<(String, Uint128)>,
+    /// The index of the leaf of each address.
+    leaf_indexes: HashMap<String, usize>,
+    /// The layers of the tree, from the leaves to the root.
+    layers: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree from the given allocations. An odd node in a layer is promoted as is to
+    /// the next one.
+    pub fn new(allocations: Vec<(String, Uint128)>) -> Result<Self, ContractError> {
+        ensure!(
+            !allocations.is_empty(),
+            ContractError::InvalidInput {
+                reason: "cannot build a merkle tree without allocations".to_string(),
+            }
+        );
+
+        let mut leaf_indexes = HashMap::with_capacity(allocations.len());
+        for (i, (address, amount)) in allocations.iter().enumerate() {
+            ensure!(
+                !amount.is_zero(),
+                ContractError::InvalidInput {
+                    reason: format!("the allocation of {address} cannot be zero"),
+                }
+            );
+            ensure!(
+                leaf_indexes.insert(address.clone(), i).is_none(),
+                ContractError::AllocationAlreadyExists {
+                    address: address.clone(),
+                }
+            );
+        }
+
+        let mut layers = vec![allocations
+            .iter()
+            .map(|(address, amount)| hash_leaf(address, *amount))
+            .collect::<Vec<_>>()];
+
+        while layers.last().is_some_and(|layer| layer.len() > 1) {
+            let next_layer = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|nodes| match nodes {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next_layer);
+        }
+
+        Ok(MerkleTree {
+            allocations,
+            leaf_indexes,
+            layers,
+        })
+    }
+
+    /// Gets the root of the tree, to be set as the campaign's merkle root.
+    pub fn root(&self) -> HexBinary {
+        HexBinary::from(self.layers.last().unwrap()[0].as_slice())
+    }
+
+    /// Gets the allocation of the given address along with its proof.
+    pub fn proof(&self, address: &str) -> Result<(Uint128, Vec<HexBinary>), ContractError> {
+        let leaf_index =
+            *self
+                .leaf_indexes
+                .get(address)
+                .ok_or_else(|| ContractError::NoAllocationFound {
+                    address: address.to_string(),
+                })?;
+
+        let mut index = leaf_index;
+        let mut proof = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(HexBinary::from(sibling.as_slice()));
+            }
+            index /= 2;
+        }
+
+        Ok((self.allocations[leaf_index].1, proof))
+    }
+}
+
+/// Verifies the allocation of `amount` to `address` is part of the tree with the given root.
+pub fn verify_proof(
+    merkle_root: &HexBinary,
+    address: &str,
+    amount: Uint128,
+    proof: &[HexBinary],
+) -> Result<(), ContractError> {
+    let root = proof
+        .iter()
+        .try_fold(hash_leaf(address, amount), |node, sibling| {
+            let sibling: Hash = sibling
+                .to_array()
+                .map_err(|_| ContractError::InvalidMerkleProof)?;
+            Ok::<_, ContractError>(hash_pair(&node, &sibling))
+        })?;
+
+    ensure!(
+        root.as_slice() == merkle_root.as_slice(),
+        ContractError::InvalidMerkleProof
+    );
+
+    Ok(())
+}
+
+/// Parses a CSV allocation list, with one `address,amount` pair per line. A header line and
+/// empty lines are skipped.
+pub fn parse_allocations_csv(csv: &str) -> Result<Vec<(String, Uint128)>, ContractError> {
+    let mut allocations = vec![];
+
+    for (i, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line.eq_ignore_ascii_case("address,amount")) {
+            continue;
+        }
+
+        let (address, amount) =
+            line.split_once(',')
+                .ok_or_else(|| ContractError::InvalidInput {
+                    reason: format!("invalid allocation on line {}: {line}", i + 1),
+                })?;
+        let amount = amount
+            .trim()
+            .parse::<Uint128>()
+            .map_err(|e| ContractError::InvalidInput {
+                reason: format!("invalid allocation amount on line {}: {e}", i + 1),
+            })?;
+
+        allocations.push((address.trim().to_string(), amount));
+    }
+
+    Ok(allocations)
+}
+
+/// Parses a JSON allocation list, i.e. an array of `[address, amount]` pairs as in
+/// [ExecuteMsg::AddAllocations](crate::msg::ExecuteMsg::AddAllocations).
+pub fn parse_allocations_json(json: &[u8]) -> Result<Vec<(String, Uint128)>, ContractError> {
+    Ok(from_json(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{HexBinary, Uint128};
+
+    use crate::error::ContractError;
+    use crate::merkle::{parse_allocations_csv, parse_allocations_json, verify_proof, MerkleTree};
+
+    const CSV: &str = "address,amount
+mantra1a,100
+mantra1b,200
+
+mantra1c,300
+mantra1d,400
+mantra1e,500
+";
+
+    #[test]
+    fn merkle_proofs_verify() {
+        let allocations = parse_allocations_csv(CSV).unwrap();
+        assert_eq!(allocations.len(), 5);
+        assert_eq!(
+            parse_allocations_json(br#"[["mantra1a","100"],["mantra1b","200"]]"#).unwrap(),
+            allocations[..2]
+        );
+
+        let tree = MerkleTree::new(allocations.clone()).unwrap();
+        let root = tree.root();
+
+        for (address, amount) in &allocations {
+            let (allocation, proof) = tree.proof(address).unwrap();
+            assert_eq!(allocation, *amount);
+            verify_proof(&root, address, allocation, &proof).unwrap();
+
+            // a different amount or address doesn't verify
+            assert!(matches!(
+                verify_proof(&root, address, allocation + Uint128::one(), &proof),
+                Err(ContractError::InvalidMerkleProof)
+            ));
+            assert!(matches!(
+                verify_proof(&root, "mantra1z", allocation, &proof),
+                Err(ContractError::InvalidMerkleProof)
+            ));
+        }
+
+        assert!(matches!(
+            tree.proof("mantra1z"),
+            Err(ContractError::NoAllocationFound { .. })
+        ));
+        assert!(matches!(
+            verify_proof(
+                &root,
+                "mantra1a",
+                Uint128::new(100),
+                &[HexBinary::from(&[1u8; 31])]
+            ),
+            Err(ContractError::InvalidMerkleProof)
+        ));
+    }
+
+    #[test]
+    fn large_merkle_tree() {
+        let allocations = (0..20_000u128)
+            .map(|i| (format!("mantra1addr{i}"), Uint128::new(i + 1)))
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(allocations).unwrap();
+        let root = tree.root();
+
+        for address in ["mantra1addr0", "mantra1addr12345", "mantra1addr19999"] {
+            let (allocation, proof) = tree.proof(address).unwrap();
+            verify_proof(&root, address, allocation, &proof).unwrap();
+        }
+    }
+
+    #[test]
+    fn invalid_allocation_lists() {
+        assert!(parse_allocations_csv("mantra1a;100").is_err());
+        assert!(parse_allocations_csv("mantra1a,-100").is_err());
+        assert!(MerkleTree::new(vec![]).is_err());
+        assert!(matches!(
+            MerkleTree::new(vec![
+                ("mantra1a".to_string(), Uint128::new(100)),
+                ("mantra1a".to_string(), Uint128::new(200)),
+            ]),
+            Err(ContractError::AllocationAlreadyExists { .. })
+        ));
+    }
+}
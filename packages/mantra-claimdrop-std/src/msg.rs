@@ -1,9 +1,10 @@
 use std::fmt::{Display, Formatter};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{ensure, Coin, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{ensure, Coin, Decimal, HexBinary, Timestamp, Uint128};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
+use crate::attestation::{Attestation, Attester};
 use crate::error::ContractError;
 
 /// Maximum length for campaign name
@@ -12,6 +13,10 @@ const MAX_NAME_LENGTH: usize = 200;
 const MAX_DESCRIPTION_LENGTH: usize = 2000;
 /// Maximum length for campaign type
 const MAX_TYPE_LENGTH: usize = 200;
+/// Maximum number of distribution types of a campaign
+const MAX_DISTRIBUTION_TYPES: usize = 10;
+/// Maximum number of points of a custom distribution schedule
+const MAX_CUSTOM_POINTS: usize = 100;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -33,8 +38,13 @@ pub enum ExecuteMsg {
         receiver: Option<String>,
         /// The amount to claim. If not set, all available tokens will be claimed.
         amount: Option<Uint128>,
+        /// The proof of the sender's allocation, required when the campaign has a merkle root.
+        proof: Option<AllocationProof>,
+        /// The attestation of the sender's allocation, required when the campaign has an attester.
+        attestation: Option<Attestation>,
     },
-    /// Adds a batch of addresses and their allocations. This can only be done before the campaign has started.
+    /// Adds a batch of addresses and their allocations. This can only be done before the campaign has started,
+    /// and not on campaigns with a merkle root or an attester.
     AddAllocations {
         /// Vector of (address, amount) pairs
         allocations: Vec<(String, Uint128)>,
@@ -46,6 +56,13 @@ pub enum ExecuteMsg {
         /// The new address to use
         new_address: String,
     },
+    /// Raises the minimum nonce of the attestations accepted on claim, invalidating every attestation
+    /// with a lower nonce. Used to reduce or revoke attested allocations. Only campaigns with an
+    /// attester.
+    RaiseMinAttestationNonce {
+        /// The new minimum nonce, which must be greater than the current one
+        nonce: u64,
+    },
     /// Removes an address in the allocation list. This can only be done before the campaign has started.
     RemoveAddress {
         /// The address to remove
@@ -182,6 +199,15 @@ pub struct AuthorizedWalletsResponse {
     pub wallets: Vec<String>,
 }
 
+/// The proof of an allocation in a campaign with a merkle root, see [crate::merkle].
+#[cw_serde]
+pub struct AllocationProof {
+    /// The total amount allocated to the address.
+    pub allocation: Uint128,
+    /// The sibling hashes from the allocation leaf up to the merkle root.
+    pub proof: Vec<HexBinary>,
+}
+
 /// The campaign action that can be executed with the [ExecuteMsg::ManageCampaign] message.
 #[cw_serde]
 pub enum CampaignAction {
@@ -217,13 +243,22 @@ pub struct Campaign {
     pub end_time: u64,
     /// The timestamp at which the campaign was closed, in seconds
     pub closed: Option<u64>,
+    /// The merkle root of the allocations. If set, allocations are proven on claim instead of
+    /// being added on-chain.
+    pub merkle_root: Option<HexBinary>,
+    /// The signer attesting allocations off-chain. If set, allocations are attested on claim
+    /// instead of being added on-chain.
+    pub attester: Option<Attester>,
+    /// The minimum nonce of the attestations accepted on claim, see [Attestation].
+    #[serde(default)]
+    pub min_attestation_nonce: u64,
 }
 
 impl Display for Campaign {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Campaign {{ name: {}, description: {}, type: {}, total_reward: {}, claimed: {}, distribution_type: {:?}, start_time: {}, end_time: {}, closed: {:?} }}",
+            "Campaign {{ name: {}, description: {}, type: {}, total_reward: {}, claimed: {}, distribution_type: {:?}, start_time: {}, end_time: {}, closed: {:?}, merkle_root: {:?}, attester: {:?}, min_attestation_nonce: {} }}",
             self.name,
             self.description,
             self.ty,
@@ -232,7 +267,10 @@ impl Display for Campaign {
             self.distribution_type,
             self.start_time,
             self.end_time,
-            self.closed
+            self.closed,
+            self.merkle_root,
+            self.attester,
+            self.min_attestation_nonce
         )
     }
 }
@@ -255,6 +293,9 @@ impl Campaign {
             start_time: params.start_time,
             end_time: params.end_time,
             closed: None,
+            merkle_root: params.merkle_root,
+            attester: params.attester,
+            min_attestation_nonce: 0,
         }
     }
 
@@ -267,6 +308,29 @@ impl Campaign {
     pub fn has_ended(&self, current_time: &Timestamp) -> bool {
         current_time.seconds() >= self.end_time
     }
+
+    /// Raises the minimum nonce of the attestations accepted on claim
+    pub fn raise_min_attestation_nonce(&mut self, nonce: u64) -> Result<(), ContractError> {
+        ensure!(
+            self.attester.is_some(),
+            ContractError::CampaignError {
+                reason: "the campaign has no attester".to_string(),
+            }
+        );
+        ensure!(
+            nonce > self.min_attestation_nonce,
+            ContractError::InvalidInput {
+                reason: format!(
+                    "the minimum attestation nonce must be greater than {}",
+                    self.min_attestation_nonce
+                ),
+            }
+        );
+
+        self.min_attestation_nonce = nonce;
+
+        Ok(())
+    }
 }
 
 /// Represents the parameters to create a campaign with.
@@ -290,6 +354,12 @@ pub struct CampaignParams {
     pub end_time: u64,
     /// An optional label to be used for the instantiated claimdrop contract
     pub contract_label: String,
+    /// The merkle root of the allocations, built with [crate::merkle::MerkleTree]. If set,
+    /// allocations are proven on claim instead of being added on-chain.
+    pub merkle_root: Option<HexBinary>,
+    /// The signer attesting allocations off-chain, see [crate::attestation]. If set, allocations
+    /// are attested on claim instead of being added on-chain. Cannot be set along a merkle root.
+    pub attester: Option<Attester>,
 }
 
 impl CampaignParams {
@@ -379,26 +449,37 @@ impl CampaignParams {
         let mut total_percentage = Decimal::zero();
 
         ensure!(
-            !self.distribution_type.is_empty() && self.distribution_type.len() <= 2,
+            !self.distribution_type.is_empty()
+                && self.distribution_type.len() <= MAX_DISTRIBUTION_TYPES,
             ContractError::InvalidCampaignParam {
                 param: "distribution_type".to_string(),
-                reason: "invalid number of distribution types, should be at least 1, maximum 2"
-                    .to_string(),
+                reason: format!(
+                    "invalid number of distribution types, should be at least 1, maximum {}",
+                    MAX_DISTRIBUTION_TYPES
+                ),
             }
         );
 
         for dist in self.distribution_type.iter() {
+            dist.validate_schedule()?;
+
             let (percentage, start_time, end_time, cliff_duration) = match dist {
                 DistributionType::LinearVesting {
                     percentage,
                     start_time,
                     end_time,
                     cliff_duration,
-                } => (percentage, start_time, Some(end_time), cliff_duration),
+                } => (*percentage, *start_time, Some(*end_time), *cliff_duration),
                 DistributionType::LumpSum {
                     percentage,
                     start_time,
-                } => (percentage, start_time, None, &None),
+                } => (*percentage, *start_time, None, None),
+                DistributionType::PeriodicVesting { .. } | DistributionType::Custom { .. } => (
+                    dist.percentage(),
+                    dist.start_time(),
+                    Some(dist.end_time()),
+                    None,
+                ),
             };
 
             ensure!(
@@ -406,30 +487,30 @@ impl CampaignParams {
                 ContractError::ZeroDistributionPercentage
             );
 
-            total_percentage = total_percentage.checked_add(*percentage)?;
+            total_percentage = total_percentage.checked_add(percentage)?;
 
             ensure!(
-                *start_time >= self.start_time,
+                start_time >= self.start_time,
                 ContractError::InvalidStartDistributionTime {
-                    start_time: *start_time,
+                    start_time,
                     campaign_start_time: self.start_time,
                 }
             );
 
-            // validate the end time. Applies for the linear vesting distribution type only
+            // validate the end time. Applies for the distribution types vesting over time only
             if let Some(end_time) = end_time {
                 ensure!(
                     end_time > start_time,
                     ContractError::InvalidDistributionTimes {
-                        start_time: *start_time,
-                        end_time: *end_time,
+                        start_time,
+                        end_time,
                     }
                 );
 
                 ensure!(
-                    *end_time <= self.end_time,
+                    end_time <= self.end_time,
                     ContractError::InvalidEndDistributionTime {
-                        end_time: *end_time,
+                        end_time,
                         campaign_end_time: self.end_time,
                     }
                 );
@@ -438,7 +519,7 @@ impl CampaignParams {
             // validate the cliff duration
             if let Some(cliff_duration) = cliff_duration {
                 ensure!(
-                    *cliff_duration > 0u64,
+                    cliff_duration > 0u64,
                     ContractError::InvalidCampaignParam {
                         param: "cliff_duration".to_string(),
                         reason: "cannot be zero".to_string(),
@@ -448,7 +529,7 @@ impl CampaignParams {
                 ensure!(
                     // it is safe to unwrap because this cliff validation only applies for linear vesting,
                     // which contains an end_time
-                    *cliff_duration < end_time.unwrap() - start_time,
+                    cliff_duration < end_time.unwrap() - start_time,
                     ContractError::InvalidCampaignParam {
                         param: "cliff_duration".to_string(),
                         reason: "cannot be greater or equal than the distribution duration"
@@ -469,6 +550,38 @@ impl CampaignParams {
         Ok(())
     }
 
+    /// Validates the merkle root, if any, is a sha256 hash
+    pub fn validate_merkle_root(&self) -> Result<(), ContractError> {
+        if let Some(merkle_root) = &self.merkle_root {
+            ensure!(
+                merkle_root.len() == 32,
+                ContractError::InvalidCampaignParam {
+                    param: "merkle_root".to_string(),
+                    reason: "must be a 32 bytes hash".to_string(),
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Validates the attester, if any, and that it's not set along a merkle root
+    pub fn validate_attester(&self) -> Result<(), ContractError> {
+        if let Some(attester) = &self.attester {
+            ensure!(
+                self.merkle_root.is_none(),
+                ContractError::InvalidCampaignParam {
+                    param: "attester".to_string(),
+                    reason: "cannot be set along a merkle root".to_string(),
+                }
+            );
+
+            attester.validate()?;
+        }
+
+        Ok(())
+    }
+
     /// Validates the total reward amount
     pub fn validate_rewards(&self) -> Result<(), ContractError> {
         ensure!(
@@ -502,15 +615,154 @@ pub enum DistributionType {
         /// The unix timestamp when this distribution type starts, in seconds
         start_time: u64,
     },
+    /// The distribution is done in equal tranches unlocked at the end of every period, e.g.
+    /// monthly unlocks
+    PeriodicVesting {
+        /// The percentage of the total reward to be distributed in periodic tranches
+        percentage: Decimal,
+        /// The unix timestamp when this distribution type starts, in seconds. The first tranche
+        /// unlocks one period after it.
+        start_time: u64,
+        /// The duration of each period, in seconds
+        period: u64,
+        /// The number of periods, i.e. of tranches
+        num_periods: u64,
+    },
+    /// The distribution follows an arbitrary piecewise linear schedule
+    Custom {
+        /// The points of the schedule, sorted by time, at least two of them. A single unlock is a
+        /// [DistributionType::LumpSum] instead. The vested percentage is interpolated
+        /// linearly between two points, nothing is vested before the first point, and the
+        /// percentage of the last point is the percentage of the total reward distributed by the
+        /// schedule.
+        points: Vec<VestingPoint>,
+    },
+}
+
+/// A point of a [DistributionType::Custom] schedule.
+#[cw_serde]
+pub struct VestingPoint {
+    /// The unix timestamp of the point, in seconds
+    pub time: u64,
+    /// The percentage of the total reward vested at the given time, cumulative
+    pub percentage: Decimal,
 }
 
 impl DistributionType {
     pub fn has_started(&self, current_time: &Timestamp) -> bool {
-        let start_time = match self {
-            DistributionType::LinearVesting { start_time, .. } => start_time,
-            DistributionType::LumpSum { start_time, .. } => start_time,
-        };
+        current_time.seconds() >= self.start_time()
+    }
+
+    /// Gets the percentage of the total reward distributed by this distribution type
+    pub fn percentage(&self) -> Decimal {
+        match self {
+            DistributionType::LinearVesting { percentage, .. }
+            | DistributionType::LumpSum { percentage, .. }
+            | DistributionType::PeriodicVesting { percentage, .. } => *percentage,
+            DistributionType::Custom { points } => points
+                .last()
+                .map(|point| point.percentage)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Gets the unix timestamp when this distribution type starts, in seconds
+    pub fn start_time(&self) -> u64 {
+        match self {
+            DistributionType::LinearVesting { start_time, .. }
+            | DistributionType::LumpSum { start_time, .. }
+            | DistributionType::PeriodicVesting { start_time, .. } => *start_time,
+            DistributionType::Custom { points } => {
+                points.first().map(|point| point.time).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Gets the unix timestamp when this distribution type has vested its whole percentage, in
+    /// seconds
+    pub fn end_time(&self) -> u64 {
+        match self {
+            DistributionType::LinearVesting { end_time, .. } => *end_time,
+            DistributionType::LumpSum { start_time, .. } => *start_time,
+            DistributionType::PeriodicVesting {
+                start_time,
+                period,
+                num_periods,
+                ..
+            } => start_time.saturating_add(period.saturating_mul(*num_periods)),
+            DistributionType::Custom { points } => {
+                points.last().map(|point| point.time).unwrap_or_default()
+            }
+        }
+    }
+
+    /// Validates the parameters specific to the periodic and custom distribution types
+    pub fn validate_schedule(&self) -> Result<(), ContractError> {
+        match self {
+            DistributionType::PeriodicVesting {
+                start_time,
+                period,
+                num_periods,
+                ..
+            } => {
+                ensure!(
+                    *period > 0u64,
+                    ContractError::InvalidCampaignParam {
+                        param: "period".to_string(),
+                        reason: "cannot be zero".to_string(),
+                    }
+                );
+                ensure!(
+                    *num_periods > 0u64,
+                    ContractError::InvalidCampaignParam {
+                        param: "num_periods".to_string(),
+                        reason: "cannot be zero".to_string(),
+                    }
+                );
+                ensure!(
+                    period
+                        .checked_mul(*num_periods)
+                        .and_then(|duration| start_time.checked_add(duration))
+                        .is_some(),
+                    ContractError::InvalidCampaignParam {
+                        param: "num_periods".to_string(),
+                        reason: "the distribution end time overflows".to_string(),
+                    }
+                );
+            }
+            DistributionType::Custom { points } => {
+                ensure!(
+                    points.len() >= 2 && points.len() <= MAX_CUSTOM_POINTS,
+                    ContractError::InvalidCampaignParam {
+                        param: "points".to_string(),
+                        reason: format!(
+                            "invalid number of points, should be at least 2, maximum {}",
+                            MAX_CUSTOM_POINTS
+                        ),
+                    }
+                );
+                ensure!(
+                    points
+                        .windows(2)
+                        .all(|points| points[0].time < points[1].time),
+                    ContractError::InvalidCampaignParam {
+                        param: "points".to_string(),
+                        reason: "times must be strictly increasing".to_string(),
+                    }
+                );
+                ensure!(
+                    points
+                        .windows(2)
+                        .all(|points| points[0].percentage <= points[1].percentage),
+                    ContractError::InvalidCampaignParam {
+                        param: "points".to_string(),
+                        reason: "percentages cannot decrease".to_string(),
+                    }
+                );
+            }
+            DistributionType::LinearVesting { .. } | DistributionType::LumpSum { .. } => {}
+        }
 
-        current_time.seconds() >= *start_time
+        Ok(())
     }
 }
@@ -0,0 +1,26 @@
+//! Fixtures shared by the unit tests of the crate.
+
+use cosmwasm_std::coin;
+
+use crate::msg::{Campaign, CampaignParams, DistributionType};
+
+/// The parameters of an airdrop of 1_000_000 uom running from 1_000 to 10_000.
+pub(crate) fn params(distribution_type: Vec<DistributionType>) -> CampaignParams {
+    CampaignParams {
+        name: "Airdrop".to_string(),
+        description: "Airdrop".to_string(),
+        ty: "airdrop".to_string(),
+        total_reward: coin(1_000_000, "uom"),
+        distribution_type,
+        start_time: 1_000,
+        end_time: 10_000,
+        contract_label: "airdrop".to_string(),
+        merkle_root: None,
+        attester: None,
+    }
+}
+
+/// The campaign created with [params].
+pub(crate) fn campaign(distribution_type: Vec<DistributionType>) -> Campaign {
+    Campaign::from_params(params(distribution_type))
+}
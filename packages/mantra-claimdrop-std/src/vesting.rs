@@ -0,0 +1,387 @@
+use cosmwasm_std::{coin, ensure, Coin, Timestamp, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::{Campaign, DistributionType, RewardsResponse};
+
+/// Computes the rewards of an address at the given time, i.e. what the campaign's `Rewards` query
+/// returns, so the contract and clients share the same math.
+///
+/// `allocation` is the total amount allocated to the address, and `claimed` the amount it has
+/// already claimed. Each distribution type vests its share of the allocation, rounded down, and
+/// the whole allocation is vested once every distribution type has ended, so rounding never
+/// leaves dust behind. Nothing is available to claim on a closed campaign.
+pub fn claimable_at(
+    campaign: &Campaign,
+    allocation: Uint128,
+    claimed: Uint128,
+    now: &Timestamp,
+) -> Result<RewardsResponse, ContractError> {
+    ensure!(claimed <= allocation, ContractError::ExceededMaxClaimAmount);
+
+    let denom = &campaign.total_reward.denom;
+    let pending = allocation.checked_sub(claimed)?;
+
+    let available_to_claim = if campaign.closed.is_some() {
+        Uint128::zero()
+    } else {
+        compute_vested_amount(&campaign.distribution_type, allocation, now)?.saturating_sub(claimed)
+    };
+
+    Ok(RewardsResponse {
+        claimed: non_zero_coins(claimed, denom),
+        pending: non_zero_coins(pending, denom),
+        available_to_claim: non_zero_coins(available_to_claim, denom),
+    })
+}
+
+/// Computes the amount of the allocation vested at the given time across all distribution types.
+pub fn compute_vested_amount(
+    distribution_types: &[DistributionType],
+    allocation: Uint128,
+    now: &Timestamp,
+) -> Result<Uint128, ContractError> {
+    if distribution_types
+        .iter()
+        .all(|distribution_type| distribution_type.has_ended(now))
+    {
+        return Ok(allocation);
+    }
+
+    distribution_types
+        .iter()
+        .try_fold(Uint128::zero(), |vested, distribution_type| {
+            Ok(vested.checked_add(distribution_type.vested_amount(allocation, now)?)?)
+        })
+}
+
+impl DistributionType {
+    /// Checks if the distribution type has fully vested its share at the given time
+    pub fn has_ended(&self, current_time: &Timestamp) -> bool {
+        current_time.seconds() >= self.end_time()
+    }
+
+    /// Computes the amount of the allocation this distribution type has vested at the given time,
+    /// rounded down.
+    pub fn vested_amount(
+        &self,
+        allocation: Uint128,
+        current_time: &Timestamp,
+    ) -> Result<Uint128, ContractError> {
+        let now = current_time.seconds();
+
+        match self {
+            DistributionType::LinearVesting {
+                percentage,
+                start_time,
+                end_time,
+                cliff_duration,
+            } => {
+                let cliff_end_time = start_time.saturating_add(cliff_duration.unwrap_or_default());
+                if now < *start_time || now < cliff_end_time {
+                    return Ok(Uint128::zero());
+                }
+
+                let share = allocation.mul_floor(*percentage);
+                if now >= *end_time {
+                    return Ok(share);
+                }
+
+                Ok(share.checked_multiply_ratio(now - start_time, end_time - start_time)?)
+            }
+            DistributionType::LumpSum {
+                percentage,
+                start_time,
+            } => {
+                if now < *start_time {
+                    return Ok(Uint128::zero());
+                }
+
+                Ok(allocation.mul_floor(*percentage))
+            }
+            DistributionType::PeriodicVesting {
+                percentage,
+                start_time,
+                period,
+                num_periods,
+            } => {
+                if now < *start_time {
+                    return Ok(Uint128::zero());
+                }
+
+                let elapsed_periods = (now - start_time)
+                    .checked_div(*period)
+                    .unwrap_or(*num_periods)
+                    .min(*num_periods);
+
+                Ok(allocation
+                    .mul_floor(*percentage)
+                    .checked_multiply_ratio(elapsed_periods, *num_periods)?)
+            }
+            DistributionType::Custom { points } => {
+                // the points surrounding the current time
+                let next_index = points.partition_point(|point| point.time <= now);
+                let Some(previous) = next_index.checked_sub(1).map(|i| &points[i]) else {
+                    return Ok(Uint128::zero());
+                };
+
+                let previous_amount = allocation.mul_floor(previous.percentage);
+                let Some(next) = points.get(next_index) else {
+                    return Ok(previous_amount);
+                };
+
+                let next_amount = allocation.mul_floor(next.percentage);
+                Ok(previous_amount.checked_add(
+                    next_amount
+                        .checked_sub(previous_amount)?
+                        .checked_multiply_ratio(now - previous.time, next.time - previous.time)?,
+                )?)
+            }
+        }
+    }
+}
+
+/// Wraps the amount in a coin, or in no coin at all if it's zero.
+fn non_zero_coins(amount: Uint128, denom: &str) -> Vec<Coin> {
+    if amount.is_zero() {
+        vec![]
+    } else {
+        vec![coin(amount.u128(), denom)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Decimal, Timestamp, Uint128};
+
+    use crate::error::ContractError;
+    use crate::msg::{Campaign, DistributionType, RewardsResponse, VestingPoint};
+    use crate::testing::{campaign, params};
+    use crate::vesting::claimable_at;
+
+    fn invalid_param(distribution_type: Vec<DistributionType>) -> String {
+        match params(distribution_type).validate_campaign_distribution() {
+            Err(ContractError::InvalidCampaignParam { param, .. }) => param,
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
+
+    fn available(campaign: &Campaign, allocation: u128, claimed: u128, now: u64) -> u128 {
+        claimable_at(
+            campaign,
+            Uint128::new(allocation),
+            Uint128::new(claimed),
+            &Timestamp::from_seconds(now),
+        )
+        .unwrap()
+        .available_to_claim
+        .first()
+        .map_or(0, |coin| coin.amount.u128())
+    }
+
+    #[test]
+    fn linear_vesting_with_cliff() {
+        let campaign = campaign(vec![DistributionType::LinearVesting {
+            percentage: Decimal::percent(100),
+            start_time: 1_000,
+            end_time: 2_000,
+            cliff_duration: Some(250),
+        }]);
+
+        assert_eq!(available(&campaign, 1_000, 0, 999), 0);
+        assert_eq!(available(&campaign, 1_000, 0, 1_249), 0);
+        // the cliff releases what vested during it
+        assert_eq!(available(&campaign, 1_000, 0, 1_250), 250);
+        assert_eq!(available(&campaign, 1_000, 250, 1_500), 250);
+        assert_eq!(available(&campaign, 1_000, 0, 5_000), 1_000);
+
+        assert_eq!(
+            claimable_at(
+                &campaign,
+                Uint128::new(1_000),
+                Uint128::new(250),
+                &Timestamp::from_seconds(1_500)
+            )
+            .unwrap(),
+            RewardsResponse {
+                claimed: vec![coin(250, "uom")],
+                pending: vec![coin(750, "uom")],
+                available_to_claim: vec![coin(250, "uom")],
+            }
+        );
+    }
+
+    #[test]
+    fn multiple_distribution_types_round_down() {
+        let campaign = campaign(vec![
+            DistributionType::LumpSum {
+                percentage: Decimal::percent(33),
+                start_time: 1_000,
+            },
+            DistributionType::LinearVesting {
+                percentage: Decimal::percent(67),
+                start_time: 1_000,
+                end_time: 4_000,
+                cliff_duration: None,
+            },
+        ]);
+
+        assert_eq!(available(&campaign, 1_001, 0, 1_000), 330);
+        // 330 + floor(670 * 1 / 3)
+        assert_eq!(available(&campaign, 1_001, 0, 2_000), 553);
+        assert_eq!(available(&campaign, 1_001, 0, 3_999), 999);
+        // once every distribution ended, the rounding dust is vested too
+        assert_eq!(available(&campaign, 1_001, 0, 4_000), 1_001);
+        assert_eq!(available(&campaign, 1_001, 1_001, 4_000), 0);
+    }
+
+    #[test]
+    fn closed_campaign_and_invalid_claimed() {
+        let mut campaign = campaign(vec![DistributionType::LumpSum {
+            percentage: Decimal::percent(100),
+            start_time: 1_000,
+        }]);
+        assert_eq!(available(&campaign, 1_000, 0, 1_000), 1_000);
+
+        campaign.closed = Some(1_500);
+        assert_eq!(available(&campaign, 1_000, 0, 2_000), 0);
+
+        assert!(matches!(
+            claimable_at(
+                &campaign,
+                Uint128::new(1_000),
+                Uint128::new(1_001),
+                &Timestamp::from_seconds(2_000)
+            ),
+            Err(ContractError::ExceededMaxClaimAmount)
+        ));
+    }
+
+    #[test]
+    fn periodic_vesting() {
+        let campaign = campaign(vec![DistributionType::PeriodicVesting {
+            percentage: Decimal::percent(100),
+            start_time: 1_000,
+            period: 100,
+            num_periods: 3,
+        }]);
+
+        assert_eq!(available(&campaign, 1_000, 0, 1_099), 0);
+        assert_eq!(available(&campaign, 1_000, 0, 1_100), 333);
+        assert_eq!(available(&campaign, 1_000, 0, 1_299), 666);
+        assert_eq!(available(&campaign, 1_000, 0, 1_300), 1_000);
+        assert_eq!(available(&campaign, 1_000, 0, 9_000), 1_000);
+    }
+
+    #[test]
+    fn custom_schedule() {
+        let point = |time, percentage| VestingPoint {
+            time,
+            percentage: Decimal::percent(percentage),
+        };
+        let campaign = campaign(vec![
+            DistributionType::Custom {
+                points: vec![point(1_000, 10), point(2_000, 10), point(3_000, 50)],
+            },
+            DistributionType::LumpSum {
+                percentage: Decimal::percent(50),
+                start_time: 5_000,
+            },
+        ]);
+
+        assert_eq!(available(&campaign, 1_000, 0, 999), 0);
+        // the first point unlocks right away, then nothing vests until the second point
+        assert_eq!(available(&campaign, 1_000, 0, 1_000), 100);
+        assert_eq!(available(&campaign, 1_000, 0, 1_999), 100);
+        assert_eq!(available(&campaign, 1_000, 0, 2_500), 300);
+        assert_eq!(available(&campaign, 1_000, 0, 4_000), 500);
+        assert_eq!(available(&campaign, 1_000, 0, 5_000), 1_000);
+    }
+
+    #[test]
+    fn invalid_periodic_vesting() {
+        let periodic = |start_time, period, num_periods| DistributionType::PeriodicVesting {
+            percentage: Decimal::percent(100),
+            start_time,
+            period,
+            num_periods,
+        };
+
+        params(vec![periodic(1_000, 100, 90)])
+            .validate_campaign_distribution()
+            .unwrap();
+
+        assert_eq!(invalid_param(vec![periodic(1_000, 0, 3)]), "period");
+        assert_eq!(invalid_param(vec![periodic(1_000, 100, 0)]), "num_periods");
+        assert_eq!(
+            invalid_param(vec![periodic(1_000, u64::MAX / 2, 3)]),
+            "num_periods"
+        );
+        assert_eq!(
+            invalid_param(vec![periodic(1_000, u64::MAX - 999, 1)]),
+            "num_periods"
+        );
+    }
+
+    #[test]
+    fn invalid_custom_schedule() {
+        let point = |time, percentage| VestingPoint {
+            time,
+            percentage: Decimal::percent(percentage),
+        };
+        let custom = |points| DistributionType::Custom { points };
+
+        params(vec![custom(vec![point(1_000, 10), point(2_000, 100)])])
+            .validate_campaign_distribution()
+            .unwrap();
+
+        assert_eq!(invalid_param(vec![custom(vec![])]), "points");
+        // a single point is a lump sum
+        assert_eq!(
+            invalid_param(vec![custom(vec![point(1_000, 100)])]),
+            "points"
+        );
+        assert_eq!(
+            invalid_param(vec![custom(
+                (0..101).map(|i| point(1_000 + i, 100)).collect()
+            )]),
+            "points"
+        );
+        assert_eq!(
+            invalid_param(vec![custom(vec![point(2_000, 10), point(1_000, 100)])]),
+            "points"
+        );
+        assert_eq!(
+            invalid_param(vec![custom(vec![
+                point(1_000, 10),
+                point(1_000, 50),
+                point(2_000, 100)
+            ])]),
+            "points"
+        );
+        assert_eq!(
+            invalid_param(vec![custom(vec![
+                point(1_000, 60),
+                point(2_000, 50),
+                point(3_000, 100)
+            ])]),
+            "points"
+        );
+    }
+
+    #[test]
+    fn too_many_distribution_types() {
+        let lump_sum = |percentage| DistributionType::LumpSum {
+            percentage: Decimal::percent(percentage),
+            start_time: 1_000,
+        };
+
+        params(vec![lump_sum(10); 10])
+            .validate_campaign_distribution()
+            .unwrap();
+
+        assert_eq!(invalid_param(vec![]), "distribution_type");
+        let mut distribution_type = vec![lump_sum(9); 10];
+        distribution_type.push(lump_sum(10));
+        assert_eq!(invalid_param(distribution_type), "distribution_type");
+    }
+}
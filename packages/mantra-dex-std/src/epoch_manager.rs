@@ -3,7 +3,7 @@ use std::fmt;
 use std::fmt::Display;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Deps, StdResult, Timestamp, Uint64};
+use cosmwasm_std::{ensure, Deps, StdError, StdResult, Timestamp, Uint64};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
 #[cw_serde]
@@ -90,6 +90,87 @@ impl Display for EpochConfig {
     }
 }
 
+impl EpochConfig {
+    /// Validates the epoch configuration.
+    pub fn validate(&self) -> StdResult<()> {
+        ensure!(
+            !self.duration.is_zero(),
+            StdError::generic_err("The epoch duration cannot be zero")
+        );
+        Ok(())
+    }
+
+    /// Gets the id of the epoch the given time falls in. Epoch 0 starts at the genesis epoch, and
+    /// there is no epoch before it, so times before the genesis epoch are rejected.
+    pub fn epoch_id_at(&self, time: &Timestamp) -> StdResult<u64> {
+        self.validate()?;
+
+        let elapsed_time = time
+            .seconds()
+            .checked_sub(self.genesis_epoch.u64())
+            .ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "The time {} is before the genesis epoch {}",
+                    time.seconds(),
+                    self.genesis_epoch
+                ))
+            })?;
+        Ok(elapsed_time / self.duration.u64())
+    }
+
+    /// Gets the epoch the given time falls in, as the epoch manager's `CurrentEpoch` query would
+    /// return it at that time. Times before the genesis epoch are rejected.
+    pub fn epoch_at(&self, time: &Timestamp) -> StdResult<Epoch> {
+        let id = self.epoch_id_at(time)?;
+
+        Ok(Epoch {
+            id,
+            start_time: self.epoch_start_time(id)?,
+        })
+    }
+
+    /// Gets the time at which the given epoch starts.
+    pub fn epoch_start_time(&self, epoch_id: u64) -> StdResult<Timestamp> {
+        self.validate()?;
+
+        let start_time = self
+            .duration
+            .checked_mul(Uint64::new(epoch_id))?
+            .checked_add(self.genesis_epoch)?;
+        Ok(Timestamp::from_seconds(start_time.u64()))
+    }
+
+    /// Gets the time at which the given epoch ends, i.e. the start time of the next epoch.
+    pub fn epoch_end_time(&self, epoch_id: u64) -> StdResult<Timestamp> {
+        let next_epoch_id = epoch_id
+            .checked_add(1)
+            .ok_or_else(|| StdError::generic_err("Epoch id overflow"))?;
+        self.epoch_start_time(next_epoch_id)
+    }
+
+    /// Gets the number of epochs that start after `start_time`, up to `end_time` inclusive. The
+    /// times can be before the genesis epoch, in which case the genesis epoch is counted if it
+    /// starts in the range.
+    pub fn epochs_between(&self, start_time: &Timestamp, end_time: &Timestamp) -> StdResult<u64> {
+        ensure!(
+            start_time <= end_time,
+            StdError::generic_err("The start time cannot be later than the end time")
+        );
+
+        Ok(self.epochs_started_by(end_time)? - self.epochs_started_by(start_time)?)
+    }
+
+    /// Gets the number of epochs that have started by the given time, inclusive.
+    fn epochs_started_by(&self, time: &Timestamp) -> StdResult<u64> {
+        if time.seconds() < self.genesis_epoch.u64() {
+            self.validate()?;
+            return Ok(0);
+        }
+
+        Ok(self.epoch_id_at(time)? + 1)
+    }
+}
+
 pub type ConfigResponse = Config;
 
 /// The contract configuration.
@@ -114,3 +195,120 @@ pub fn get_current_epoch(deps: Deps, epoch_manager_addr: String) -> StdResult<Ep
 
     Ok(epoch_response.epoch)
 }
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{StdError, Timestamp, Uint64};
+
+    use crate::epoch_manager::{Epoch, EpochConfig};
+
+    fn epoch_config() -> EpochConfig {
+        EpochConfig {
+            duration: Uint64::new(86_400),
+            genesis_epoch: Uint64::new(1_000_000),
+        }
+    }
+
+    #[test]
+    fn epoch_at_time() {
+        let config = epoch_config();
+
+        // there is no epoch before genesis
+        assert_eq!(
+            config.epoch_at(&Timestamp::from_seconds(999_999)),
+            Err(StdError::generic_err(
+                "The time 999999 is before the genesis epoch 1000000"
+            ))
+        );
+        assert_eq!(
+            config
+                .epoch_at(&Timestamp::from_seconds(1_000_000))
+                .unwrap(),
+            Epoch {
+                id: 0,
+                start_time: Timestamp::from_seconds(1_000_000),
+            }
+        );
+        assert_eq!(
+            config
+                .epoch_id_at(&Timestamp::from_seconds(1_086_399))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            config
+                .epoch_at(&Timestamp::from_seconds(1_000_000 + 86_400 * 10 + 5))
+                .unwrap(),
+            Epoch {
+                id: 10,
+                start_time: Timestamp::from_seconds(1_864_000),
+            }
+        );
+        assert_eq!(
+            config.epoch_end_time(10).unwrap(),
+            Timestamp::from_seconds(1_950_400)
+        );
+    }
+
+    #[test]
+    fn epochs_between_times() {
+        let config = epoch_config();
+
+        assert_eq!(
+            config
+                .epochs_between(
+                    &Timestamp::from_seconds(0),
+                    &Timestamp::from_seconds(1_086_400)
+                )
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            config
+                .epochs_between(
+                    &Timestamp::from_seconds(0),
+                    &Timestamp::from_seconds(999_999)
+                )
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            config
+                .epochs_between(
+                    &Timestamp::from_seconds(1_000_000),
+                    &Timestamp::from_seconds(1_086_400)
+                )
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            config
+                .epochs_between(
+                    &Timestamp::from_seconds(1_086_399),
+                    &Timestamp::from_seconds(1_086_399)
+                )
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            config.epochs_between(&Timestamp::from_seconds(2), &Timestamp::from_seconds(1)),
+            Err(StdError::generic_err(
+                "The start time cannot be later than the end time"
+            ))
+        );
+
+        let invalid_config = EpochConfig {
+            duration: Uint64::zero(),
+            genesis_epoch: Uint64::zero(),
+        };
+        assert!(invalid_config
+            .epoch_id_at(&Timestamp::from_seconds(1))
+            .is_err());
+        assert!(EpochConfig {
+            duration: Uint64::zero(),
+            genesis_epoch: Uint64::new(10),
+        }
+        .epochs_between(&Timestamp::from_seconds(0), &Timestamp::from_seconds(1))
+        .is_err());
+    }
+}
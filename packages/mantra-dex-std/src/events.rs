@@ -0,0 +1,734 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{attr, ensure, Attribute, Coin, Event, StdError, StdResult, Uint128};
+
+use crate::farm_manager::{MERGE_POSITIONS_ACTION, TRANSFER_POSITION_ACTION};
+
+/// The type of the events emitted by contracts.
+pub const WASM_EVENT_TYPE: &str = "wasm";
+/// The attribute identifying the action that emitted the event.
+pub const ACTION_ATTRIBUTE: &str = "action";
+/// The separator of the values of attributes holding lists.
+const LIST_SEPARATOR: &str = ",";
+
+/// An event emitted by one of the contracts, identified by its `action` attribute. Contracts
+/// emit the event with [WasmEvent::to_attributes], and consumers read it back with
+/// [WasmEvent::try_from_event] or `TryFrom<&Event>`.
+pub trait WasmEvent: Sized {
+    /// The value of the `action` attribute of the event.
+    const ACTION: &'static str;
+
+    /// Parses the event out of the attributes of an event, already checked to be a `wasm` event
+    /// for [WasmEvent::ACTION].
+    fn from_attributes(event: &Event) -> StdResult<Self>;
+
+    /// Parses the event out of the attributes of a `wasm` event, checking it's for
+    /// [WasmEvent::ACTION].
+    fn try_from_event(event: &Event) -> StdResult<Self> {
+        ensure_action(event, Self::ACTION)?;
+        Self::from_attributes(event)
+    }
+
+    /// Gets the attributes to emit for the event, starting with the `action` attribute.
+    fn to_attributes(&self) -> Vec<Attribute>;
+}
+
+/// Parses all the events of type `T` out of the given events, e.g. the events of a transaction
+/// or the ones in a `SubMsgResponse`. Events emitted by other actions are ignored.
+pub fn parse_wasm_events<T: WasmEvent>(events: &[Event]) -> StdResult<Vec<T>> {
+    events
+        .iter()
+        .filter(|event| is_action_event(event, T::ACTION))
+        .map(T::try_from_event)
+        .collect()
+}
+
+/// Parses the first event of type `T` out of the given events, erroring if there's none.
+pub fn parse_wasm_event<T: WasmEvent>(events: &[Event]) -> StdResult<T> {
+    events
+        .iter()
+        .find(|event| is_action_event(event, T::ACTION))
+        .ok_or_else(|| StdError::generic_err(format!("No {} event found", T::ACTION)))
+        .and_then(T::try_from_event)
+}
+
+/// The data of a swap, emitted by the pool manager.
+#[cw_serde]
+pub struct SwapEvent {
+    /// The address that performed the swap.
+    pub sender: String,
+    /// The address that received the swapped tokens.
+    pub receiver: String,
+    /// The identifier of the pool the swap was performed in.
+    pub pool_identifier: String,
+    /// The asset offered in the swap.
+    pub offer_asset: Coin,
+    /// The asset returned by the swap, after fees.
+    pub return_asset: Coin,
+    /// The slippage amount of the swap.
+    pub slippage_amount: Uint128,
+    /// The swap fee amount of the swap.
+    pub swap_fee_amount: Uint128,
+    /// The protocol fee amount of the swap.
+    pub protocol_fee_amount: Uint128,
+    /// The burn fee amount of the swap.
+    pub burn_fee_amount: Uint128,
+    /// The extra fees amount of the swap.
+    pub extra_fees_amount: Uint128,
+}
+
+impl WasmEvent for SwapEvent {
+    const ACTION: &'static str = "swap";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(SwapEvent {
+            sender: get_attribute(event, "sender")?.to_string(),
+            receiver: get_attribute(event, "receiver")?.to_string(),
+            pool_identifier: get_attribute(event, "pool_identifier")?.to_string(),
+            offer_asset: Coin {
+                denom: get_attribute(event, "offer_denom")?.to_string(),
+                amount: parse_attribute(event, "offer_amount")?,
+            },
+            return_asset: Coin {
+                denom: get_attribute(event, "ask_denom")?.to_string(),
+                amount: parse_attribute(event, "return_amount")?,
+            },
+            slippage_amount: parse_attribute(event, "slippage_amount")?,
+            swap_fee_amount: parse_attribute(event, "swap_fee_amount")?,
+            protocol_fee_amount: parse_attribute(event, "protocol_fee_amount")?,
+            burn_fee_amount: parse_attribute(event, "burn_fee_amount")?,
+            extra_fees_amount: parse_attribute(event, "extra_fees_amount")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("sender", &self.sender),
+            attr("receiver", &self.receiver),
+            attr("pool_identifier", &self.pool_identifier),
+            attr("offer_denom", &self.offer_asset.denom),
+            attr("offer_amount", self.offer_asset.amount),
+            attr("ask_denom", &self.return_asset.denom),
+            attr("return_amount", self.return_asset.amount),
+            attr("slippage_amount", self.slippage_amount),
+            attr("swap_fee_amount", self.swap_fee_amount),
+            attr("protocol_fee_amount", self.protocol_fee_amount),
+            attr("burn_fee_amount", self.burn_fee_amount),
+            attr("extra_fees_amount", self.extra_fees_amount),
+        ]
+    }
+}
+
+/// The data of a liquidity provision, emitted by the pool manager.
+#[cw_serde]
+pub struct ProvideLiquidityEvent {
+    /// The address that provided the liquidity.
+    pub sender: String,
+    /// The address that received the LP tokens.
+    pub receiver: String,
+    /// The identifier of the pool the liquidity was provided to.
+    pub pool_identifier: String,
+    /// The amount of LP tokens minted.
+    pub added_shares: Uint128,
+}
+
+impl WasmEvent for ProvideLiquidityEvent {
+    const ACTION: &'static str = "provide_liquidity";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(ProvideLiquidityEvent {
+            sender: get_attribute(event, "sender")?.to_string(),
+            receiver: get_attribute(event, "receiver")?.to_string(),
+            pool_identifier: get_attribute(event, "pool_identifier")?.to_string(),
+            added_shares: parse_attribute(event, "added_shares")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("sender", &self.sender),
+            attr("receiver", &self.receiver),
+            attr("pool_identifier", &self.pool_identifier),
+            attr("added_shares", self.added_shares),
+        ]
+    }
+}
+
+/// The data of a liquidity withdrawal, emitted by the pool manager.
+#[cw_serde]
+pub struct WithdrawLiquidityEvent {
+    /// The address that withdrew the liquidity.
+    pub sender: String,
+    /// The identifier of the pool the liquidity was withdrawn from.
+    pub pool_identifier: String,
+    /// The amount of LP tokens burned.
+    pub withdrawn_share: Uint128,
+}
+
+impl WasmEvent for WithdrawLiquidityEvent {
+    const ACTION: &'static str = "withdraw_liquidity";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(WithdrawLiquidityEvent {
+            sender: get_attribute(event, "sender")?.to_string(),
+            pool_identifier: get_attribute(event, "pool_identifier")?.to_string(),
+            withdrawn_share: parse_attribute(event, "withdrawn_share")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("sender", &self.sender),
+            attr("pool_identifier", &self.pool_identifier),
+            attr("withdrawn_share", self.withdrawn_share),
+        ]
+    }
+}
+
+/// The data of a pool creation, emitted by the pool manager.
+#[cw_serde]
+pub struct CreatePoolEvent {
+    /// The address that created the pool.
+    pub sender: String,
+    /// The identifier of the pool.
+    pub pool_identifier: String,
+    /// The asset denoms of the pool.
+    pub asset_denoms: Vec<String>,
+    /// The LP denom of the pool.
+    pub lp_denom: String,
+    /// The label of the pool type, see [PoolType::get_label](crate::pool_manager::PoolType::get_label).
+    pub pool_type: String,
+}
+
+impl WasmEvent for CreatePoolEvent {
+    const ACTION: &'static str = "create_pool";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(CreatePoolEvent {
+            sender: get_attribute(event, "sender")?.to_string(),
+            pool_identifier: get_attribute(event, "pool_identifier")?.to_string(),
+            asset_denoms: parse_list(event, "asset_denoms")?,
+            lp_denom: get_attribute(event, "lp_denom")?.to_string(),
+            pool_type: get_attribute(event, "pool_type")?.to_string(),
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("sender", &self.sender),
+            attr("pool_identifier", &self.pool_identifier),
+            attr("asset_denoms", self.asset_denoms.join(LIST_SEPARATOR)),
+            attr("lp_denom", &self.lp_denom),
+            attr("pool_type", &self.pool_type),
+        ]
+    }
+}
+
+/// The data of a farm creation or expansion, emitted by the farm manager.
+#[cw_serde]
+pub struct FillFarmEvent {
+    /// The address that filled the farm.
+    pub sender: String,
+    /// The identifier of the farm.
+    pub farm_identifier: String,
+    /// The LP denom the farm is for.
+    pub lp_denom: String,
+    /// The asset added to the farm.
+    pub farm_asset: Coin,
+    /// The epoch at which the farm starts.
+    pub start_epoch: u64,
+    /// The epoch at which the farm preliminarily ends.
+    pub preliminary_end_epoch: u64,
+}
+
+impl WasmEvent for FillFarmEvent {
+    const ACTION: &'static str = "fill_farm";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(FillFarmEvent {
+            sender: get_attribute(event, "sender")?.to_string(),
+            farm_identifier: get_attribute(event, "farm_identifier")?.to_string(),
+            lp_denom: get_attribute(event, "lp_denom")?.to_string(),
+            farm_asset: parse_attribute(event, "farm_asset")?,
+            start_epoch: parse_attribute(event, "start_epoch")?,
+            preliminary_end_epoch: parse_attribute(event, "preliminary_end_epoch")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("sender", &self.sender),
+            attr("farm_identifier", &self.farm_identifier),
+            attr("lp_denom", &self.lp_denom),
+            attr("farm_asset", self.farm_asset.to_string()),
+            attr("start_epoch", self.start_epoch.to_string()),
+            attr(
+                "preliminary_end_epoch",
+                self.preliminary_end_epoch.to_string(),
+            ),
+        ]
+    }
+}
+
+/// The data of a rewards claim, emitted by the farm manager.
+#[cw_serde]
+pub struct ClaimEvent {
+    /// The address that claimed the rewards.
+    pub receiver: String,
+    /// The claimed rewards.
+    pub rewards: Vec<Coin>,
+}
+
+impl WasmEvent for ClaimEvent {
+    const ACTION: &'static str = "claim";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(ClaimEvent {
+            receiver: get_attribute(event, "receiver")?.to_string(),
+            rewards: parse_list(event, "rewards")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("receiver", &self.receiver),
+            attr("rewards", join_list(&self.rewards)),
+        ]
+    }
+}
+
+/// The data of a position being opened, emitted by the farm manager.
+#[cw_serde]
+pub struct OpenPositionEvent {
+    /// The owner of the position.
+    pub receiver: String,
+    /// The identifier of the position.
+    pub identifier: String,
+    /// The LP asset locked in the position.
+    pub lp_asset: Coin,
+    /// The unlocking duration of the position, in seconds.
+    pub unlocking_duration: u64,
+}
+
+impl WasmEvent for OpenPositionEvent {
+    const ACTION: &'static str = "open_position";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(OpenPositionEvent {
+            receiver: get_attribute(event, "receiver")?.to_string(),
+            identifier: get_attribute(event, "identifier")?.to_string(),
+            lp_asset: parse_attribute(event, "lp_asset")?,
+            unlocking_duration: parse_attribute(event, "unlocking_duration")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("receiver", &self.receiver),
+            attr("identifier", &self.identifier),
+            attr("lp_asset", self.lp_asset.to_string()),
+            attr("unlocking_duration", self.unlocking_duration.to_string()),
+        ]
+    }
+}
+
+/// The data of a position being expanded, emitted by the farm manager.
+#[cw_serde]
+pub struct ExpandPositionEvent {
+    /// The owner of the position.
+    pub receiver: String,
+    /// The identifier of the position.
+    pub identifier: String,
+    /// The LP asset added to the position.
+    pub lp_asset: Coin,
+}
+
+impl WasmEvent for ExpandPositionEvent {
+    const ACTION: &'static str = "expand_position";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(ExpandPositionEvent {
+            receiver: get_attribute(event, "receiver")?.to_string(),
+            identifier: get_attribute(event, "identifier")?.to_string(),
+            lp_asset: parse_attribute(event, "lp_asset")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("receiver", &self.receiver),
+            attr("identifier", &self.identifier),
+            attr("lp_asset", self.lp_asset.to_string()),
+        ]
+    }
+}
+
+/// The data of a position being closed, emitted by the farm manager.
+#[cw_serde]
+pub struct ClosePositionEvent {
+    /// The owner of the position.
+    pub receiver: String,
+    /// The identifier of the position.
+    pub identifier: String,
+    /// The LP asset closed, which can be part of the position.
+    pub lp_asset: Coin,
+}
+
+impl WasmEvent for ClosePositionEvent {
+    const ACTION: &'static str = "close_position";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(ClosePositionEvent {
+            receiver: get_attribute(event, "receiver")?.to_string(),
+            identifier: get_attribute(event, "identifier")?.to_string(),
+            lp_asset: parse_attribute(event, "lp_asset")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("receiver", &self.receiver),
+            attr("identifier", &self.identifier),
+            attr("lp_asset", self.lp_asset.to_string()),
+        ]
+    }
+}
+
+/// The data of a position being withdrawn, emitted by the farm manager.
+#[cw_serde]
+pub struct WithdrawPositionEvent {
+    /// The owner of the position.
+    pub receiver: String,
+    /// The identifier of the position.
+    pub identifier: String,
+    /// The LP asset returned to the owner.
+    pub lp_asset: Coin,
+    /// The amount of LP tokens taken as emergency unlock penalty, if any.
+    pub emergency_unlock_penalty: Uint128,
+}
+
+impl WasmEvent for WithdrawPositionEvent {
+    const ACTION: &'static str = "withdraw_position";
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(WithdrawPositionEvent {
+            receiver: get_attribute(event, "receiver")?.to_string(),
+            identifier: get_attribute(event, "identifier")?.to_string(),
+            lp_asset: parse_attribute(event, "lp_asset")?,
+            emergency_unlock_penalty: parse_attribute(event, "emergency_unlock_penalty")?,
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("receiver", &self.receiver),
+            attr("identifier", &self.identifier),
+            attr("lp_asset", self.lp_asset.to_string()),
+            attr("emergency_unlock_penalty", self.emergency_unlock_penalty),
+        ]
+    }
+}
+
+/// The data of a position transfer, emitted by the farm manager.
+#[cw_serde]
+pub struct TransferPositionEvent {
+    /// The identifier of the position.
+    pub identifier: String,
+    /// The previous owner of the position.
+    pub previous_receiver: String,
+    /// The new owner of the position.
+    pub new_receiver: String,
+}
+
+impl WasmEvent for TransferPositionEvent {
+    const ACTION: &'static str = TRANSFER_POSITION_ACTION;
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(TransferPositionEvent {
+            identifier: get_attribute(event, "identifier")?.to_string(),
+            previous_receiver: get_attribute(event, "previous_receiver")?.to_string(),
+            new_receiver: get_attribute(event, "new_receiver")?.to_string(),
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("identifier", &self.identifier),
+            attr("previous_receiver", &self.previous_receiver),
+            attr("new_receiver", &self.new_receiver),
+        ]
+    }
+}
+
+/// The data of a positions merge, emitted by the farm manager.
+#[cw_serde]
+pub struct MergePositionsEvent {
+    /// The identifier of the position the others were merged into.
+    pub identifier: String,
+    /// The identifiers of the positions merged and removed.
+    pub merged_identifiers: Vec<String>,
+    /// The LP asset of the merged position.
+    pub lp_asset: Coin,
+    /// The owner of the positions.
+    pub receiver: String,
+}
+
+impl WasmEvent for MergePositionsEvent {
+    const ACTION: &'static str = MERGE_POSITIONS_ACTION;
+
+    fn from_attributes(event: &Event) -> StdResult<Self> {
+        Ok(MergePositionsEvent {
+            identifier: get_attribute(event, "identifier")?.to_string(),
+            merged_identifiers: parse_list(event, "merged_identifiers")?,
+            lp_asset: parse_attribute(event, "lp_asset")?,
+            receiver: get_attribute(event, "receiver")?.to_string(),
+        })
+    }
+
+    fn to_attributes(&self) -> Vec<Attribute> {
+        vec![
+            attr(ACTION_ATTRIBUTE, Self::ACTION),
+            attr("identifier", &self.identifier),
+            attr(
+                "merged_identifiers",
+                self.merged_identifiers.join(LIST_SEPARATOR),
+            ),
+            attr("lp_asset", self.lp_asset.to_string()),
+            attr("receiver", &self.receiver),
+        ]
+    }
+}
+
+/// Implements `TryFrom<&Event>` for the given [WasmEvent]s, with [WasmEvent::try_from_event].
+macro_rules! impl_try_from_event {
+    ($($event:ty),+ $(,)?) => {
+        $(
+            impl TryFrom<&Event> for $event {
+                type Error = StdError;
+
+                fn try_from(event: &Event) -> StdResult<Self> {
+                    <$event as WasmEvent>::try_from_event(event)
+                }
+            }
+        )+
+    };
+}
+
+impl_try_from_event!(
+    SwapEvent,
+    ProvideLiquidityEvent,
+    WithdrawLiquidityEvent,
+    CreatePoolEvent,
+    FillFarmEvent,
+    ClaimEvent,
+    OpenPositionEvent,
+    ExpandPositionEvent,
+    ClosePositionEvent,
+    WithdrawPositionEvent,
+    TransferPositionEvent,
+    MergePositionsEvent,
+);
+
+/// Checks whether the given event is a `wasm` event for the given action.
+fn is_action_event(event: &Event, action: &str) -> bool {
+    event.ty == WASM_EVENT_TYPE
+        && event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == ACTION_ATTRIBUTE && attr.value == action)
+}
+
+/// Ensures the given event is a `wasm` event for the given action.
+fn ensure_action(event: &Event, action: &str) -> StdResult<()> {
+    ensure!(
+        is_action_event(event, action),
+        StdError::generic_err(format!("The event is not a {action} event"))
+    );
+    Ok(())
+}
+
+/// Gets the value of the given attribute of the event.
+fn get_attribute<'a>(event: &'a Event, key: &str) -> StdResult<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+        .ok_or_else(|| StdError::generic_err(format!("Missing event attribute {key}")))
+}
+
+/// Parses the comma separated values of the given attribute of the event. An empty value is an
+/// empty list.
+fn parse_list<T>(event: &Event, key: &str) -> StdResult<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = get_attribute(event, key)?;
+    if value.is_empty() {
+        return Ok(vec![]);
+    }
+
+    value
+        .split(LIST_SEPARATOR)
+        .map(|item| {
+            item.parse::<T>().map_err(|e| {
+                StdError::generic_err(format!("Invalid value for event attribute {key}: {e}"))
+            })
+        })
+        .collect()
+}
+
+/// Joins the given values into a comma separated attribute value.
+fn join_list<T: Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(LIST_SEPARATOR)
+}
+
+/// Parses the value of the given attribute of the event.
+fn parse_attribute<T>(event: &Event, key: &str) -> StdResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    get_attribute(event, key)?
+        .parse::<T>()
+        .map_err(|e| StdError::generic_err(format!("Invalid value for event attribute {key}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Event, StdError, Uint128};
+
+    use crate::events::{
+        parse_wasm_event, parse_wasm_events, ClaimEvent, CreatePoolEvent, FillFarmEvent,
+        MergePositionsEvent, ProvideLiquidityEvent, SwapEvent, WasmEvent, WithdrawPositionEvent,
+    };
+
+    fn swap_event(offer_amount: &str) -> Event {
+        Event::new("wasm").add_attributes(vec![
+            ("action", "swap"),
+            ("sender", "sender"),
+            ("receiver", "receiver"),
+            ("offer_denom", "uom"),
+            ("ask_denom", "uusdc"),
+            ("offer_amount", offer_amount),
+            ("return_amount", "998"),
+            ("slippage_amount", "1"),
+            ("swap_fee_amount", "1"),
+            ("protocol_fee_amount", "0"),
+            ("burn_fee_amount", "0"),
+            ("extra_fees_amount", "0"),
+            ("pool_identifier", "p.1"),
+        ])
+    }
+
+    #[test]
+    fn parse_swap_events() {
+        let events = vec![
+            Event::new("message").add_attribute("action", "/cosmwasm.wasm.v1.MsgExecuteContract"),
+            swap_event("1000"),
+            swap_event("2000"),
+        ];
+
+        let swaps: Vec<SwapEvent> = parse_wasm_events(&events).unwrap();
+        assert_eq!(swaps.len(), 2);
+        assert_eq!(swaps[0].offer_asset, coin(1_000, "uom"));
+        assert_eq!(swaps[0].return_asset, coin(998, "uusdc"));
+        assert_eq!(swaps[0].swap_fee_amount, Uint128::one());
+        assert_eq!(swaps[1].offer_asset, coin(2_000, "uom"));
+    }
+
+    #[test]
+    fn parse_provide_liquidity_event() {
+        let events = vec![Event::new("wasm").add_attributes(vec![
+            ("action", "provide_liquidity"),
+            ("sender", "sender"),
+            ("receiver", "receiver"),
+            ("pool_identifier", "p.1"),
+            ("added_shares", "1000"),
+        ])];
+
+        let event: ProvideLiquidityEvent = parse_wasm_event(&events).unwrap();
+        assert_eq!(event.added_shares, Uint128::new(1_000));
+        assert_eq!(event.receiver, "receiver");
+
+        assert_eq!(
+            parse_wasm_event::<SwapEvent>(&events),
+            Err(StdError::generic_err("No swap event found"))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_events() {
+        assert!(SwapEvent::try_from_event(&swap_event("invalid")).is_err());
+        assert_eq!(
+            ProvideLiquidityEvent::try_from_event(&swap_event("1000")),
+            Err(StdError::generic_err(
+                "The event is not a provide_liquidity event"
+            ))
+        );
+    }
+
+    fn round_trip<T: WasmEvent + PartialEq + std::fmt::Debug>(event: T) {
+        let wasm_event = Event::new("wasm").add_attributes(event.to_attributes());
+        assert_eq!(T::try_from_event(&wasm_event).unwrap(), event);
+    }
+
+    #[test]
+    fn events_round_trip() {
+        round_trip(SwapEvent::try_from(&swap_event("1000")).unwrap());
+
+        round_trip(CreatePoolEvent {
+            sender: "sender".to_string(),
+            pool_identifier: "o.uom.uusdc".to_string(),
+            asset_denoms: vec!["uom".to_string(), "uusdc".to_string()],
+            lp_denom: "factory/pool_manager/o.uom.uusdc.LP".to_string(),
+            pool_type: "ConstantProduct".to_string(),
+        });
+        round_trip(FillFarmEvent {
+            sender: "sender".to_string(),
+            farm_identifier: "m-farm".to_string(),
+            lp_denom: "factory/pool_manager/o.uom.uusdc.LP".to_string(),
+            farm_asset: coin(1_000, "uom"),
+            start_epoch: 10,
+            preliminary_end_epoch: 24,
+        });
+        round_trip(ClaimEvent {
+            receiver: "receiver".to_string(),
+            rewards: vec![coin(1_000, "uom"), coin(20, "uusdc")],
+        });
+        round_trip(ClaimEvent {
+            receiver: "receiver".to_string(),
+            rewards: vec![],
+        });
+        round_trip(WithdrawPositionEvent {
+            receiver: "receiver".to_string(),
+            identifier: "u-position".to_string(),
+            lp_asset: coin(900, "factory/pool_manager/o.uom.uusdc.LP"),
+            emergency_unlock_penalty: Uint128::new(100),
+        });
+        round_trip(MergePositionsEvent {
+            identifier: "u-position_1".to_string(),
+            merged_identifiers: vec!["u-position_2".to_string(), "u-position_3".to_string()],
+            lp_asset: coin(3_000, "factory/pool_manager/o.uom.uusdc.LP"),
+            receiver: "receiver".to_string(),
+        });
+    }
+}
@@ -1,10 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cosmwasm_std::{
+    ensure, Addr, Attribute, Coin, Decimal, Decimal256, StdError, StdResult, Uint128, Uint256,
+};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
+use crate::constants::DAY_IN_SECONDS;
+use crate::events::{MergePositionsEvent, TransferPositionEvent, WasmEvent};
+
 /// The instantiation message
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -249,6 +254,56 @@ pub enum PositionAction {
         /// unlocked immediately. If the position has not expired, it will pay a penalty.
         emergency_unlock: Option<bool>,
     },
+    /// Transfers an open position to a new receiver, keeping its LP tokens locked and its
+    /// unlocking duration.
+    Transfer {
+        /// The identifier of the position.
+        identifier: String,
+        /// The address that will own the position.
+        new_receiver: String,
+    },
+    /// Merges open positions with the same LP denom and unlocking duration into the first one.
+    /// The other positions are removed.
+    Merge {
+        /// The identifiers of the positions to merge, at least two.
+        identifiers: Vec<String>,
+    },
+}
+
+/// The value of the `action` attribute emitted when transferring a position.
+pub const TRANSFER_POSITION_ACTION: &str = "transfer_position";
+/// The value of the `action` attribute emitted when merging positions.
+pub const MERGE_POSITIONS_ACTION: &str = "merge_positions";
+
+impl PositionAction {
+    /// Validates the parameters of the action that don't depend on the contract's state.
+    pub fn validate(&self) -> StdResult<()> {
+        match self {
+            PositionAction::Transfer { new_receiver, .. } => {
+                ensure!(
+                    !new_receiver.is_empty(),
+                    StdError::generic_err("The new receiver of the position cannot be empty")
+                );
+            }
+            PositionAction::Merge { identifiers } => {
+                ensure!(
+                    identifiers.len() >= 2,
+                    StdError::generic_err("At least two positions are needed to merge")
+                );
+
+                let mut unique_identifiers = identifiers.clone();
+                unique_identifiers.sort();
+                unique_identifiers.dedup();
+                ensure!(
+                    unique_identifiers.len() == identifiers.len(),
+                    StdError::generic_err("Cannot merge a position with itself")
+                );
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
 }
 
 // type for the epoch id
@@ -269,7 +324,8 @@ pub struct Farm {
     pub claimed_amount: Uint128,
     /// The amount of the `farm_asset` that is to be distributed every epoch.
     pub emission_rate: Uint128,
-    /// The type of curve the farm has.
+    /// The type of curve the farm has. It defines how the `farm_asset` is distributed over the
+    /// epochs, see [Farm::get_emission].
     pub curve: Curve,
     /// The epoch at which the farm starts.
     pub start_epoch: EpochId,
@@ -277,16 +333,200 @@ pub struct Farm {
     pub preliminary_end_epoch: EpochId,
 }
 
+impl Farm {
+    /// Gets the amount of the farm asset to be distributed on the given epoch, according to the
+    /// farm's curve. Epochs outside of the farm's duration, i.e. `start_epoch` inclusive to
+    /// `preliminary_end_epoch` exclusive, don't get any emissions.
+    pub fn get_emission(&self, epoch_id: EpochId) -> StdResult<Uint128> {
+        if epoch_id < self.start_epoch || epoch_id >= self.preliminary_end_epoch {
+            return Ok(Uint128::zero());
+        }
+
+        let amount = self.farm_asset.amount;
+        let duration = self.preliminary_end_epoch - self.start_epoch;
+        let elapsed_epochs = epoch_id - self.start_epoch;
+
+        match &self.curve {
+            Curve::Linear => Ok(self.emission_rate),
+            Curve::ExponentialDecay { decay_rate } => {
+                // emission = amount * decay_rate * (1 - decay_rate)^elapsed_epochs / (1 - (1 - decay_rate)^duration),
+                // so the emissions over the whole duration add up to the farm amount
+                let retention = Decimal256::one().checked_sub((*decay_rate).into())?;
+                let normalization = Decimal256::one().checked_sub(retention.checked_pow(
+                    u32::try_from(duration).map_err(|_| {
+                        StdError::generic_err("The farm duration is too long for this curve")
+                    })?,
+                )?)?;
+                let share = Decimal256::from(*decay_rate)
+                    .checked_mul(retention.checked_pow(elapsed_epochs as u32)?)?;
+
+                Ok(Uint128::try_from(
+                    Uint256::from(amount)
+                        .checked_multiply_ratio(share.atomics(), normalization.atomics())
+                        .map_err(|e| StdError::generic_err(e.to_string()))?,
+                )?)
+            }
+            Curve::Step { step_epochs } => {
+                ensure!(
+                    *step_epochs > 0u64,
+                    StdError::generic_err("The step epochs cannot be zero")
+                );
+
+                // the emissions are released at the last epoch of every step
+                if (elapsed_epochs + 1) % step_epochs != 0 {
+                    return Ok(Uint128::zero());
+                }
+
+                Ok(amount.checked_div(Uint128::from(duration / step_epochs))?)
+            }
+            Curve::Piecewise { segments } => {
+                let mut segment_start = 0u64;
+                for segment in segments {
+                    let segment_end = segment_start
+                        .checked_add(segment.epochs)
+                        .ok_or_else(|| StdError::generic_err("Curve segments epochs overflow"))?;
+
+                    if elapsed_epochs < segment_end {
+                        return Ok(amount
+                            .mul_floor(segment.percentage)
+                            .checked_div(Uint128::from(segment.epochs))?);
+                    }
+
+                    segment_start = segment_end;
+                }
+
+                Ok(Uint128::zero())
+            }
+        }
+    }
+
+    /// Validates that the farm's curve is valid for its duration, and that the total emissions
+    /// over the farm's duration don't exceed the farm amount.
+    pub fn validate_emissions(&self) -> StdResult<()> {
+        ensure!(
+            self.start_epoch < self.preliminary_end_epoch,
+            StdError::generic_err("The farm start epoch must be lower than its end epoch")
+        );
+
+        self.curve
+            .validate(self.preliminary_end_epoch - self.start_epoch)?;
+
+        let mut total_emission = Uint128::zero();
+        for epoch_id in self.start_epoch..self.preliminary_end_epoch {
+            total_emission = total_emission.checked_add(self.get_emission(epoch_id)?)?;
+        }
+
+        ensure!(
+            total_emission <= self.farm_asset.amount,
+            StdError::generic_err(format!(
+                "The farm emissions ({total_emission}) exceed the farm amount ({})",
+                self.farm_asset.amount
+            ))
+        );
+
+        Ok(())
+    }
+}
+
 #[cw_serde]
 pub enum Curve {
     /// A linear curve that releases assets uniformly over time.
     Linear,
+    /// A front-loaded curve, where each epoch releases `decay_rate` less than the previous one.
+    ExponentialDecay {
+        /// The share by which the emissions decrease on every epoch. Must be between 0 and 1,
+        /// exclusive.
+        decay_rate: Decimal,
+    },
+    /// A curve that releases the assets in equal tranches, at the last epoch of every step. A
+    /// single step lasting the whole farm duration acts as a cliff.
+    Step {
+        /// The amount of epochs each step lasts. The farm duration must be a multiple of it.
+        step_epochs: u64,
+    },
+    /// A custom curve made of consecutive segments, each of them releasing a share of the assets
+    /// uniformly over its epochs.
+    Piecewise {
+        /// The segments of the curve, in order. The epochs must add up to the farm duration and
+        /// the percentages to 100%.
+        segments: Vec<CurveSegment>,
+    },
+}
+
+/// A segment of a [Curve::Piecewise] curve.
+#[cw_serde]
+pub struct CurveSegment {
+    /// The amount of epochs the segment lasts.
+    pub epochs: u64,
+    /// The share of the farm amount released during the segment.
+    pub percentage: Decimal,
+}
+
+impl Curve {
+    /// Validates the curve parameters for a farm lasting the given amount of epochs.
+    pub fn validate(&self, farm_duration: u64) -> StdResult<()> {
+        match self {
+            Curve::Linear => Ok(()),
+            Curve::ExponentialDecay { decay_rate } => {
+                ensure!(
+                    !decay_rate.is_zero() && *decay_rate < Decimal::one(),
+                    StdError::generic_err("The decay rate must be between 0 and 1, exclusive")
+                );
+                Ok(())
+            }
+            Curve::Step { step_epochs } => {
+                ensure!(
+                    *step_epochs > 0u64 && farm_duration % step_epochs == 0,
+                    StdError::generic_err(
+                        "The farm duration must be a non-zero multiple of the step epochs"
+                    )
+                );
+                Ok(())
+            }
+            Curve::Piecewise { segments } => {
+                ensure!(
+                    !segments.is_empty(),
+                    StdError::generic_err("A piecewise curve must have at least one segment")
+                );
+                ensure!(
+                    segments.iter().all(|segment| segment.epochs > 0u64),
+                    StdError::generic_err("Curve segments cannot last zero epochs")
+                );
+
+                let mut total_epochs = 0u64;
+                let mut total_percentage = Decimal::zero();
+                for segment in segments {
+                    total_epochs = total_epochs
+                        .checked_add(segment.epochs)
+                        .ok_or_else(|| StdError::generic_err("Curve segments epochs overflow"))?;
+                    total_percentage = total_percentage.checked_add(segment.percentage)?;
+                }
+
+                ensure!(
+                    total_epochs == farm_duration,
+                    StdError::generic_err(format!(
+                        "The curve segments last {total_epochs} epochs, but the farm lasts {farm_duration}"
+                    ))
+                );
+                ensure!(
+                    total_percentage == Decimal::one(),
+                    StdError::generic_err(format!(
+                        "The curve segments percentages must add up to 100%, got {total_percentage}"
+                    ))
+                );
+                Ok(())
+            }
+        }
+    }
 }
 
 impl std::fmt::Display for Curve {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Curve::Linear => write!(f, "linear"),
+            Curve::ExponentialDecay { .. } => write!(f, "exponential_decay"),
+            Curve::Step { .. } => write!(f, "step"),
+            Curve::Piecewise { .. } => write!(f, "piecewise"),
         }
     }
 }
@@ -312,6 +552,226 @@ impl Position {
     pub fn is_expired(&self, current_time: u64) -> bool {
         self.expiring_at.is_some() && self.expiring_at.unwrap() <= current_time
     }
+
+    /// Transfers the position to `new_receiver`, on behalf of `sender`. Only open positions can
+    /// be transferred, by their current receiver.
+    pub fn transfer(&mut self, sender: &Addr, new_receiver: Addr) -> StdResult<Vec<Attribute>> {
+        ensure!(
+            self.receiver == sender,
+            StdError::generic_err(format!(
+                "Only the receiver can transfer the position {}",
+                self.identifier
+            ))
+        );
+        ensure!(
+            self.open,
+            StdError::generic_err(format!(
+                "The position {} is closed and cannot be transferred",
+                self.identifier
+            ))
+        );
+        ensure!(
+            self.receiver != new_receiver,
+            StdError::generic_err(format!(
+                "The position {} already belongs to {new_receiver}",
+                self.identifier
+            ))
+        );
+
+        let previous_receiver = std::mem::replace(&mut self.receiver, new_receiver);
+
+        Ok(TransferPositionEvent {
+            identifier: self.identifier.clone(),
+            previous_receiver: previous_receiver.into_string(),
+            new_receiver: self.receiver.to_string(),
+        }
+        .to_attributes())
+    }
+
+    /// Merges the given positions, on behalf of `sender`, into the first one, which is returned
+    /// along with the attributes to emit. The positions must be open, belong to `sender`, and
+    /// share the same LP denom and unlocking duration.
+    pub fn merge(positions: &[Position], sender: &Addr) -> StdResult<(Position, Vec<Attribute>)> {
+        let (first, others) = positions
+            .split_first()
+            .filter(|(_, others)| !others.is_empty())
+            .ok_or_else(|| StdError::generic_err("At least two positions are needed to merge"))?;
+
+        let unique_identifiers = positions
+            .iter()
+            .map(|position| position.identifier.as_str())
+            .collect::<HashSet<_>>();
+        ensure!(
+            unique_identifiers.len() == positions.len(),
+            StdError::generic_err("Cannot merge a position with itself")
+        );
+
+        let mut merged = first.clone();
+        for position in positions {
+            ensure!(
+                position.receiver == sender,
+                StdError::generic_err(format!(
+                    "Only the receiver can merge the position {}",
+                    position.identifier
+                ))
+            );
+            ensure!(
+                position.open,
+                StdError::generic_err(format!(
+                    "The position {} is closed and cannot be merged",
+                    position.identifier
+                ))
+            );
+            ensure!(
+                position.lp_asset.denom == first.lp_asset.denom
+                    && position.unlocking_duration == first.unlocking_duration,
+                StdError::generic_err(
+                    "Only positions with the same LP denom and unlocking duration can be merged"
+                )
+            );
+        }
+
+        for position in others {
+            merged.lp_asset.amount = merged
+                .lp_asset
+                .amount
+                .checked_add(position.lp_asset.amount)?;
+        }
+
+        let attributes = MergePositionsEvent {
+            identifier: merged.identifier.clone(),
+            merged_identifiers: others
+                .iter()
+                .map(|position| position.identifier.clone())
+                .collect(),
+            lp_asset: merged.lp_asset.clone(),
+            receiver: merged.receiver.to_string(),
+        }
+        .to_attributes();
+
+        Ok((merged, attributes))
+    }
+
+    /// Gets the weight the position adds to its LP denom, according to its unlocking duration,
+    /// given the `min_unlocking_duration` and `max_unlocking_duration` in the farm manager's
+    /// [Config].
+    pub fn weight(
+        &self,
+        min_unlocking_duration: u64,
+        max_unlocking_duration: u64,
+    ) -> StdResult<Uint128> {
+        calculate_weight(
+            &self.lp_asset,
+            self.unlocking_duration,
+            min_unlocking_duration,
+            max_unlocking_duration,
+        )
+    }
+
+    /// Gets the LP tokens taken as penalty when emergency unlocking the position, given the
+    /// `emergency_unlock_penalty` in the farm manager's [Config].
+    pub fn emergency_unlock_penalty(&self, emergency_unlock_penalty: Decimal) -> StdResult<Coin> {
+        ensure!(
+            emergency_unlock_penalty <= Decimal::one(),
+            StdError::generic_err("The emergency unlock penalty cannot exceed 100%")
+        );
+
+        Ok(Coin {
+            denom: self.lp_asset.denom.clone(),
+            amount: self.lp_asset.amount.mul_floor(emergency_unlock_penalty),
+        })
+    }
+}
+
+/// The unlocking duration with the lowest weight multiplier, one day in seconds.
+pub const MIN_WEIGHTED_UNLOCKING_DURATION: u64 = DAY_IN_SECONDS;
+
+/// The unlocking duration with the highest weight multiplier, one Earth rotation year in seconds.
+pub const MAX_WEIGHTED_UNLOCKING_DURATION: u64 = 31_556_926u64;
+
+/// Gets the multiplier applied to the LP tokens locked in a position for `unlocking_duration`
+/// seconds to get its weight. The unlocking duration must be within the `min_unlocking_duration`
+/// and `max_unlocking_duration` in the farm manager's [Config].
+///
+/// The multiplier goes from 1x for one day to 16x for one year, interpolating quadratically
+/// between `(86400, 1)`, `(15778458, 5)` and `(31556926, 16)`. Note that 31556926 is not exactly
+/// one 365-day year, but rather one Earth rotation year, and 15778458 is half of it. Durations
+/// are clamped to that range, so shorter ones get 1x and longer ones 16x.
+pub fn get_unlocking_duration_multiplier(
+    unlocking_duration: u64,
+    min_unlocking_duration: u64,
+    max_unlocking_duration: u64,
+) -> StdResult<Decimal256> {
+    ensure!(
+        (min_unlocking_duration..=max_unlocking_duration).contains(&unlocking_duration),
+        StdError::generic_err(format!(
+            "Invalid unlocking duration of {unlocking_duration} seconds to calculate the weight"
+        ))
+    );
+
+    let unlocking_duration = Decimal256::from_ratio(
+        unlocking_duration.clamp(
+            MIN_WEIGHTED_UNLOCKING_DURATION,
+            MAX_WEIGHTED_UNLOCKING_DURATION,
+        ),
+        1u8,
+    );
+
+    let divisor = Decimal256::raw(7_791_996_353_100_889_432_894u128);
+    let quadratic_part = unlocking_duration
+        .checked_pow(2)?
+        .checked_mul(Decimal256::raw(109_498_841u128))?
+        .checked_div(divisor)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let linear_part = unlocking_duration
+        .checked_mul(Decimal256::raw(249_042_009_202_369u128))?
+        .checked_div(divisor)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let constant_part = Decimal256::from_ratio(246_210_981_355_969u64, 246_918_738_317_569u64);
+
+    Ok(quadratic_part
+        .checked_add(linear_part)?
+        .checked_add(constant_part)?)
+}
+
+/// Calculates the weight of the given LP asset when locked for `unlocking_duration` seconds, see
+/// [get_unlocking_duration_multiplier]. The weight is never lower than the amount of LP tokens.
+pub fn calculate_weight(
+    lp_asset: &Coin,
+    unlocking_duration: u64,
+    min_unlocking_duration: u64,
+    max_unlocking_duration: u64,
+) -> StdResult<Uint128> {
+    let multiplier = get_unlocking_duration_multiplier(
+        unlocking_duration,
+        min_unlocking_duration,
+        max_unlocking_duration,
+    )?;
+
+    let weight = Uint128::try_from(
+        Decimal256::from_ratio(lp_asset.amount, 1u8)
+            .checked_mul(multiplier)?
+            .to_uint_floor(),
+    )?;
+
+    Ok(weight.max(lp_asset.amount))
+}
+
+/// Calculates the LP weight the given positions add to the given LP denom, i.e. what the
+/// `LpWeight` query returns for their owner. Only open positions have weight.
+pub fn calculate_lp_weight(
+    positions: &[Position],
+    lp_denom: &str,
+    config: &Config,
+) -> StdResult<Uint128> {
+    positions
+        .iter()
+        .filter(|position| position.open && position.lp_asset.denom == lp_denom)
+        .try_fold(Uint128::zero(), |lp_weight, position| {
+            Ok(lp_weight.checked_add(
+                position.weight(config.min_unlocking_duration, config.max_unlocking_duration)?,
+            )?)
+        })
 }
 
 impl Display for Position {
@@ -374,3 +834,318 @@ pub struct LpWeightResponse {
     /// The epoch id corresponding to the lp weight in the contract
     pub epoch_id: EpochId,
 }
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Addr, Decimal, StdError, Uint128};
+
+    use crate::constants::DAY_IN_SECONDS;
+    use crate::farm_manager::{
+        calculate_lp_weight, calculate_weight, get_unlocking_duration_multiplier, Config, Curve,
+        CurveSegment, Farm, Position, PositionAction,
+    };
+
+    fn farm(curve: Curve, amount: u128, start_epoch: u64, preliminary_end_epoch: u64) -> Farm {
+        Farm {
+            identifier: "farm".to_string(),
+            owner: Addr::unchecked("owner"),
+            lp_denom: "factory/pool_manager/om.usdc.LP".to_string(),
+            farm_asset: coin(amount, "uom"),
+            claimed_amount: Uint128::zero(),
+            emission_rate: Uint128::new(amount / u128::from(preliminary_end_epoch - start_epoch)),
+            curve,
+            start_epoch,
+            preliminary_end_epoch,
+        }
+    }
+
+    fn emissions(farm: &Farm) -> Vec<u128> {
+        (farm.start_epoch - 1..=farm.preliminary_end_epoch)
+            .map(|epoch_id| farm.get_emission(epoch_id).unwrap().u128())
+            .collect()
+    }
+
+    #[test]
+    fn linear_emissions() {
+        let farm = farm(Curve::Linear, 1_000, 10, 14);
+
+        assert_eq!(emissions(&farm), vec![0, 250, 250, 250, 250, 0]);
+        assert!(farm.validate_emissions().is_ok());
+    }
+
+    #[test]
+    fn exponential_decay_emissions() {
+        let farm = farm(
+            Curve::ExponentialDecay {
+                decay_rate: Decimal::percent(50),
+            },
+            15_000,
+            10,
+            14,
+        );
+
+        // 15_000 * 0.5 * 0.5^k / (1 - 0.5^4)
+        assert_eq!(emissions(&farm), vec![0, 8_000, 4_000, 2_000, 1_000, 0]);
+        assert!(farm.validate_emissions().is_ok());
+
+        let farm = self::farm(
+            Curve::ExponentialDecay {
+                decay_rate: Decimal::percent(10),
+            },
+            1_000_000,
+            1,
+            100,
+        );
+        let total: u128 = emissions(&farm).iter().sum();
+        assert!(total <= 1_000_000 && total > 999_900);
+        assert!(farm.validate_emissions().is_ok());
+    }
+
+    #[test]
+    fn step_emissions() {
+        let farm = farm(Curve::Step { step_epochs: 2 }, 1_000, 10, 16);
+
+        assert_eq!(emissions(&farm), vec![0, 0, 333, 0, 333, 0, 333, 0]);
+        assert!(farm.validate_emissions().is_ok());
+
+        let cliff = self::farm(Curve::Step { step_epochs: 3 }, 1_000, 10, 13);
+        assert_eq!(emissions(&cliff), vec![0, 0, 0, 1_000, 0]);
+
+        let invalid = self::farm(Curve::Step { step_epochs: 4 }, 1_000, 10, 16);
+        assert_eq!(
+            invalid.validate_emissions(),
+            Err(StdError::generic_err(
+                "The farm duration must be a non-zero multiple of the step epochs"
+            ))
+        );
+
+        // unvalidated farms error instead of panicking
+        let zero_step = self::farm(Curve::Step { step_epochs: 0 }, 1_000, 10, 16);
+        assert_eq!(
+            zero_step.get_emission(10),
+            Err(StdError::generic_err("The step epochs cannot be zero"))
+        );
+    }
+
+    #[test]
+    fn piecewise_emissions() {
+        let farm = farm(
+            Curve::Piecewise {
+                segments: vec![
+                    CurveSegment {
+                        epochs: 1,
+                        percentage: Decimal::percent(50),
+                    },
+                    CurveSegment {
+                        epochs: 2,
+                        percentage: Decimal::zero(),
+                    },
+                    CurveSegment {
+                        epochs: 2,
+                        percentage: Decimal::percent(50),
+                    },
+                ],
+            },
+            1_000,
+            10,
+            15,
+        );
+
+        assert_eq!(emissions(&farm), vec![0, 500, 0, 0, 250, 250, 0]);
+        assert!(farm.validate_emissions().is_ok());
+
+        let invalid = self::farm(
+            Curve::Piecewise {
+                segments: vec![CurveSegment {
+                    epochs: 5,
+                    percentage: Decimal::percent(90),
+                }],
+            },
+            1_000,
+            10,
+            15,
+        );
+        assert_eq!(
+            invalid.validate_emissions(),
+            Err(StdError::generic_err(
+                "The curve segments percentages must add up to 100%, got 0.9"
+            ))
+        );
+    }
+
+    #[test]
+    fn emissions_cannot_exceed_farm_amount() {
+        let mut farm = farm(Curve::Linear, 1_000, 10, 14);
+        farm.emission_rate = Uint128::new(251);
+
+        assert_eq!(
+            farm.validate_emissions(),
+            Err(StdError::generic_err(
+                "The farm emissions (1004) exceed the farm amount (1000)"
+            ))
+        );
+    }
+
+    fn config() -> Config {
+        Config {
+            fee_collector_addr: Addr::unchecked("fee_collector"),
+            epoch_manager_addr: Addr::unchecked("epoch_manager"),
+            pool_manager_addr: Addr::unchecked("pool_manager"),
+            create_farm_fee: coin(1_000, "uom"),
+            max_concurrent_farms: 5,
+            max_farm_epoch_buffer: 14,
+            min_unlocking_duration: DAY_IN_SECONDS,
+            max_unlocking_duration: 31_556_926,
+            farm_expiration_time: 30 * DAY_IN_SECONDS,
+            emergency_unlock_penalty: Decimal::percent(10),
+        }
+    }
+
+    fn position(amount: u128, unlocking_duration: u64, open: bool) -> Position {
+        Position {
+            identifier: "p_1".to_string(),
+            lp_asset: coin(amount, "lp"),
+            unlocking_duration,
+            open,
+            expiring_at: None,
+            receiver: Addr::unchecked("user"),
+        }
+    }
+
+    #[test]
+    fn weight_interpolation() {
+        let lp_asset = coin(1_000_000, "lp");
+
+        let weight = |unlocking_duration| {
+            calculate_weight(&lp_asset, unlocking_duration, DAY_IN_SECONDS, 31_556_926)
+        };
+
+        assert_eq!(weight(DAY_IN_SECONDS).unwrap(), Uint128::new(1_000_000));
+        // the interpolation constants are rounded, so the weights fall slightly short of 5x and 16x
+        assert_eq!(weight(15_778_458).unwrap(), Uint128::new(4_999_997));
+        assert_eq!(weight(31_556_926).unwrap(), Uint128::new(15_999_999));
+        assert!(weight(DAY_IN_SECONDS - 1).is_err());
+        assert!(weight(31_556_927).is_err());
+
+        // the bounds come from the farm manager config
+        assert!(calculate_weight(&lp_asset, DAY_IN_SECONDS, DAY_IN_SECONDS, 15_778_458).is_ok());
+        assert!(calculate_weight(&lp_asset, 31_556_926, DAY_IN_SECONDS, 15_778_458).is_err());
+
+        // durations out of the curve range are clamped to it
+        assert_eq!(
+            get_unlocking_duration_multiplier(3_600, 3_600, 31_556_926).unwrap(),
+            get_unlocking_duration_multiplier(DAY_IN_SECONDS, 3_600, 31_556_926).unwrap()
+        );
+        assert_eq!(
+            calculate_weight(&lp_asset, 3_600, 3_600, 31_556_926).unwrap(),
+            Uint128::new(1_000_000)
+        );
+        assert_eq!(
+            calculate_weight(&lp_asset, 2 * 31_556_926, DAY_IN_SECONDS, 2 * 31_556_926).unwrap(),
+            Uint128::new(15_999_999)
+        );
+    }
+
+    #[test]
+    fn lp_weight_and_penalty() {
+        let positions = vec![
+            position(1_000, DAY_IN_SECONDS, true),
+            position(1_000, 31_556_926, true),
+            position(5_000, DAY_IN_SECONDS, false),
+        ];
+
+        assert_eq!(
+            calculate_lp_weight(&positions, "lp", &config()).unwrap(),
+            Uint128::new(16_999)
+        );
+        assert_eq!(
+            calculate_lp_weight(&positions, "other_lp", &config()).unwrap(),
+            Uint128::zero()
+        );
+
+        assert_eq!(
+            positions[0]
+                .emergency_unlock_penalty(Decimal::percent(10))
+                .unwrap(),
+            coin(100, "lp")
+        );
+        assert_eq!(
+            positions[0].emergency_unlock_penalty(Decimal::percent(101)),
+            Err(StdError::generic_err(
+                "The emergency unlock penalty cannot exceed 100%"
+            ))
+        );
+    }
+
+    #[test]
+    fn transfer_position() {
+        let user = Addr::unchecked("user");
+        let custodian = Addr::unchecked("custodian");
+        let mut position = position(1_000, DAY_IN_SECONDS, true);
+
+        assert!(position.transfer(&custodian, custodian.clone()).is_err());
+        assert!(position.transfer(&user, user.clone()).is_err());
+
+        let attributes = position.transfer(&user, custodian.clone()).unwrap();
+        assert_eq!(position.receiver, custodian);
+        assert_eq!(attributes[0].value, "transfer_position");
+        assert_eq!(attributes[2].value, "user");
+
+        position.open = false;
+        assert_eq!(
+            position.transfer(&custodian, user),
+            Err(StdError::generic_err(
+                "The position p_1 is closed and cannot be transferred"
+            ))
+        );
+    }
+
+    #[test]
+    fn merge_positions() {
+        let user = Addr::unchecked("user");
+        let mut positions = vec![
+            position(1_000, DAY_IN_SECONDS, true),
+            position(2_000, DAY_IN_SECONDS, true),
+        ];
+        positions[1].identifier = "p_2".to_string();
+
+        let (merged, attributes) = Position::merge(&positions, &user).unwrap();
+        assert_eq!(merged.identifier, "p_1");
+        assert_eq!(merged.lp_asset, coin(3_000, "lp"));
+        assert_eq!(attributes[2].value, "p_2");
+
+        assert!(Position::merge(&positions[..1], &user).is_err());
+
+        // a repeated position among the merged ones would be counted twice
+        let repeated_positions = vec![
+            positions[0].clone(),
+            positions[1].clone(),
+            positions[1].clone(),
+        ];
+        assert_eq!(
+            Position::merge(&repeated_positions, &user),
+            Err(StdError::generic_err("Cannot merge a position with itself"))
+        );
+        assert!(Position::merge(&positions, &Addr::unchecked("other")).is_err());
+
+        positions[1].unlocking_duration = 2 * DAY_IN_SECONDS;
+        assert_eq!(
+            Position::merge(&positions, &user),
+            Err(StdError::generic_err(
+                "Only positions with the same LP denom and unlocking duration can be merged"
+            ))
+        );
+
+        assert!(PositionAction::Merge {
+            identifiers: vec!["p_1".to_string(), "p_1".to_string()],
+        }
+        .validate()
+        .is_err());
+        assert!(PositionAction::Transfer {
+            identifier: "p_1".to_string(),
+            new_receiver: "".to_string(),
+        }
+        .validate()
+        .is_err());
+    }
+}
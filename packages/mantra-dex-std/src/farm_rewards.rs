@@ -0,0 +1,263 @@
+use std::collections::{BTreeMap, HashMap};
+
+use cosmwasm_std::{ensure, Coin, StdError, StdResult, Uint128};
+
+use crate::coin::aggregate_coins;
+use crate::farm_manager::{Config, EpochId, Farm, LpWeightResponse, Position, RewardsResponse};
+
+/// Calculates the rewards for the given positions, mirroring the farm manager's `Rewards` query.
+///
+/// The positions are expected to belong to the same address and to have been open during the
+/// whole epoch range, i.e. from `start_epoch` until `until_epoch`, both inclusive. `start_epoch` is
+/// usually the epoch after the last one the address claimed on.
+///
+/// `lp_weights` contains the total LP weight for every LP denom and epoch, i.e. the `LpWeight`
+/// query results for the farm manager's own address, and `config` the farm manager's config, which
+/// bounds the unlocking durations the position weights are calculated with.
+pub fn calculate_rewards(
+    farms: &[Farm],
+    positions: &[Position],
+    lp_weights: &HashMap<String, Vec<LpWeightResponse>>,
+    config: &Config,
+    start_epoch: EpochId,
+    until_epoch: EpochId,
+) -> StdResult<RewardsResponse> {
+    ensure!(
+        start_epoch <= until_epoch,
+        StdError::generic_err("The start epoch cannot be greater than the until epoch")
+    );
+
+    // the weight of the user for each lp denom, only open positions earn rewards
+    let mut user_weights: BTreeMap<&str, Uint128> = BTreeMap::new();
+    for position in positions.iter().filter(|position| position.open) {
+        let weight =
+            position.weight(config.min_unlocking_duration, config.max_unlocking_duration)?;
+        let user_weight = user_weights
+            .entry(position.lp_asset.denom.as_str())
+            .or_default();
+        *user_weight = user_weight.checked_add(weight)?;
+    }
+
+    let mut total_rewards = vec![];
+    let mut rewards_per_lp_denom = vec![];
+
+    for (lp_denom, user_weight) in user_weights {
+        let mut lp_denom_rewards = vec![];
+
+        for farm in farms.iter().filter(|farm| farm.lp_denom == lp_denom) {
+            let mut farm_rewards = Uint128::zero();
+
+            for epoch_id in start_epoch..=until_epoch {
+                let emission = farm.get_emission(epoch_id)?;
+                if emission.is_zero() {
+                    continue;
+                }
+
+                // epochs without LP weight don't distribute rewards, as in the farm manager
+                let total_lp_weight = get_total_lp_weight(lp_weights, lp_denom, epoch_id);
+                if total_lp_weight.is_zero() {
+                    continue;
+                }
+
+                ensure!(
+                    user_weight <= total_lp_weight,
+                    StdError::generic_err(format!(
+                        "The user weight for {lp_denom} exceeds the total LP weight on epoch {epoch_id}"
+                    ))
+                );
+
+                let reward = emission
+                    .checked_mul_floor((user_weight, total_lp_weight))
+                    .map_err(|e| StdError::generic_err(e.to_string()))?;
+                farm_rewards = farm_rewards.checked_add(reward)?;
+            }
+
+            ensure!(
+                farm_rewards.checked_add(farm.claimed_amount)? <= farm.farm_asset.amount,
+                StdError::generic_err(format!("Farm {} exhausted", farm.identifier))
+            );
+
+            if !farm_rewards.is_zero() {
+                lp_denom_rewards.push(Coin {
+                    denom: farm.farm_asset.denom.clone(),
+                    amount: farm_rewards,
+                });
+            }
+        }
+
+        if !lp_denom_rewards.is_empty() {
+            let lp_denom_rewards = aggregate_coins(lp_denom_rewards)?;
+            total_rewards.extend(lp_denom_rewards.clone());
+            rewards_per_lp_denom.push((lp_denom.to_string(), lp_denom_rewards));
+        }
+    }
+
+    Ok(RewardsResponse::RewardsResponse {
+        total_rewards: aggregate_coins(total_rewards)?,
+        rewards_per_lp_denom,
+    })
+}
+
+/// Gets the total LP weight for the given LP denom and epoch. Epochs without a weight recorded
+/// have no weight.
+fn get_total_lp_weight(
+    lp_weights: &HashMap<String, Vec<LpWeightResponse>>,
+    lp_denom: &str,
+    epoch_id: EpochId,
+) -> Uint128 {
+    lp_weights
+        .get(lp_denom)
+        .and_then(|weights| weights.iter().find(|w| w.epoch_id == epoch_id))
+        .map(|w| w.lp_weight)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use cosmwasm_std::{coin, Addr, Decimal, StdError, Uint128};
+
+    use crate::constants::DAY_IN_SECONDS;
+    use crate::farm_manager::{Config, Curve, Farm, LpWeightResponse, Position, RewardsResponse};
+    use crate::farm_rewards::calculate_rewards;
+
+    const LP_DENOM: &str = "factory/pool_manager/om.usdc.LP";
+
+    fn farm(identifier: &str, denom: &str, emission_rate: u128) -> Farm {
+        Farm {
+            identifier: identifier.to_string(),
+            owner: Addr::unchecked("owner"),
+            lp_denom: LP_DENOM.to_string(),
+            farm_asset: coin(emission_rate * 10, denom),
+            claimed_amount: Uint128::zero(),
+            emission_rate: Uint128::new(emission_rate),
+            curve: Curve::Linear,
+            start_epoch: 10,
+            preliminary_end_epoch: 20,
+        }
+    }
+
+    fn config() -> Config {
+        Config {
+            fee_collector_addr: Addr::unchecked("fee_collector"),
+            epoch_manager_addr: Addr::unchecked("epoch_manager"),
+            pool_manager_addr: Addr::unchecked("pool_manager"),
+            create_farm_fee: coin(1_000, "uom"),
+            max_concurrent_farms: 5,
+            max_farm_epoch_buffer: 14,
+            min_unlocking_duration: DAY_IN_SECONDS,
+            max_unlocking_duration: 31_556_926,
+            farm_expiration_time: 30 * DAY_IN_SECONDS,
+            emergency_unlock_penalty: Decimal::percent(10),
+        }
+    }
+
+    fn position(identifier: &str, amount: u128, open: bool) -> Position {
+        Position {
+            identifier: identifier.to_string(),
+            lp_asset: coin(amount, LP_DENOM),
+            unlocking_duration: DAY_IN_SECONDS,
+            open,
+            expiring_at: None,
+            receiver: Addr::unchecked("user"),
+        }
+    }
+
+    fn lp_weights(lp_weight: u128) -> HashMap<String, Vec<LpWeightResponse>> {
+        HashMap::from([(
+            LP_DENOM.to_string(),
+            (1..30)
+                .map(|epoch_id| LpWeightResponse {
+                    lp_weight: Uint128::new(lp_weight),
+                    epoch_id,
+                })
+                .collect(),
+        )])
+    }
+
+    #[test]
+    fn rewards_are_shared_by_weight() {
+        let farms = vec![
+            farm("farm_1", "uom", 1_000),
+            farm("farm_2", "uusdc", 100),
+            farm("farm_3", "uom", 500),
+        ];
+        let positions = vec![
+            position("p_1", 1_000, true),
+            position("p_2", 1_000, true),
+            position("p_3", 5_000, false),
+        ];
+
+        // the user owns half of the total weight, and claims from epoch 18 to 25, but the farms
+        // end at epoch 20
+        let rewards =
+            calculate_rewards(&farms, &positions, &lp_weights(4_000), &config(), 18, 25).unwrap();
+
+        assert_eq!(
+            rewards,
+            RewardsResponse::RewardsResponse {
+                total_rewards: vec![coin(1_500, "uom"), coin(100, "uusdc")],
+                rewards_per_lp_denom: vec![(
+                    LP_DENOM.to_string(),
+                    vec![coin(1_500, "uom"), coin(100, "uusdc")]
+                )],
+            }
+        );
+    }
+
+    #[test]
+    fn epochs_without_lp_weight_are_skipped() {
+        let farms = vec![farm("farm_1", "uom", 1_000)];
+        let positions = vec![position("p_1", 1_000, true)];
+
+        // only epochs 10 and 12 have LP weight
+        let mut lp_weights = lp_weights(2_000);
+        lp_weights
+            .get_mut(LP_DENOM)
+            .unwrap()
+            .retain(|lp_weight| [10, 12].contains(&lp_weight.epoch_id));
+
+        assert_eq!(
+            calculate_rewards(&farms, &positions, &lp_weights, &config(), 10, 12).unwrap(),
+            RewardsResponse::RewardsResponse {
+                total_rewards: vec![coin(1_000, "uom")],
+                rewards_per_lp_denom: vec![(LP_DENOM.to_string(), vec![coin(1_000, "uom")])],
+            }
+        );
+        assert_eq!(
+            calculate_rewards(&farms, &positions, &HashMap::new(), &config(), 10, 12).unwrap(),
+            RewardsResponse::RewardsResponse {
+                total_rewards: vec![],
+                rewards_per_lp_denom: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_rewards_inputs() {
+        let farms = vec![farm("farm_1", "uom", 1_000)];
+        let positions = vec![position("p_1", 1_000, true)];
+
+        assert_eq!(
+            calculate_rewards(&farms, &positions, &lp_weights(500), &config(), 10, 12),
+            Err(StdError::generic_err(format!(
+                "The user weight for {LP_DENOM} exceeds the total LP weight on epoch 10"
+            )))
+        );
+
+        let mut exhausted_farm = farm("farm_1", "uom", 1_000);
+        exhausted_farm.claimed_amount = Uint128::new(9_500);
+        assert_eq!(
+            calculate_rewards(
+                &[exhausted_farm],
+                &positions,
+                &lp_weights(1_000),
+                &config(),
+                10,
+                12
+            ),
+            Err(StdError::generic_err("Farm farm_1 exhausted"))
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::{Display, Formatter};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Decimal, Decimal256, StdError, StdResult, Uint128, Uint256};
+use cosmwasm_std::{Coin, Decimal, Decimal256, StdError, StdResult, Uint128, Uint256};
 
 #[cw_serde]
 pub struct Fee {
@@ -69,9 +69,23 @@ pub struct PoolFee {
     pub extra_fees: Vec<Fee>,
 }
 
+/// The default maximum share the fees of a pool can add up to.
+pub const DEFAULT_MAX_TOTAL_FEE: Decimal = Decimal::percent(20);
+
 impl PoolFee {
-    /// Validates the PoolFee structure to ensure the sum of all fees does not exceed 20%.
+    /// Validates the PoolFee structure to ensure the sum of all fees does not exceed
+    /// [DEFAULT_MAX_TOTAL_FEE].
     pub fn is_valid(&self) -> StdResult<()> {
+        self.is_valid_with_max_total_fee(DEFAULT_MAX_TOTAL_FEE)
+    }
+
+    /// Validates the PoolFee structure to ensure the sum of all fees does not exceed the given
+    /// maximum, which must be lower than 100%.
+    pub fn is_valid_with_max_total_fee(&self, max_total_fee: Decimal) -> StdResult<()> {
+        if max_total_fee >= Decimal::percent(100) {
+            return Err(StdError::generic_err("Invalid maximum total fee"));
+        }
+
         let mut total_share = Decimal::zero();
 
         // Validate predefined fees and accumulate their shares
@@ -88,49 +102,346 @@ impl PoolFee {
             total_share += fee.share;
         }
 
-        // Check if the total share exceeds 20%
-        if total_share > Decimal::percent(20) {
-            return Err(StdError::generic_err("Total fees cannot exceed 20%"));
+        // Check if the total share exceeds the maximum
+        if total_share > max_total_fee {
+            return Err(StdError::generic_err(format!(
+                "Total fees cannot exceed {}%",
+                max_total_fee * Decimal::from_ratio(100u128, 1u128)
+            )));
         }
 
         Ok(())
     }
 
     /// Computes and applies all defined fees to a given amount.
-    /// Returns the total amount of fees deducted.
+    /// Returns the total amount of fees deducted, i.e. [FeeBreakdown::total] of
+    /// [PoolFee::compute_fee_breakdown].
     pub fn compute_and_apply_fees(&self, amount: Uint256) -> StdResult<Uint128> {
-        let mut total_fee_amount = Uint256::zero();
+        self.compute_fee_breakdown(amount)?.total()
+    }
 
-        // Compute protocol fee
-        let protocol_fee_amount = self.protocol_fee.compute(amount)?;
-        total_fee_amount = total_fee_amount.checked_add(protocol_fee_amount)?;
+    /// Computes every fee of the pool for the given amount. Each fee is rounded down on its own,
+    /// so the total fees never exceed `amount` multiplied by [PoolFee::total_share].
+    pub fn compute_fee_breakdown(&self, amount: Uint256) -> StdResult<FeeBreakdown> {
+        let to_uint128 = |fee_amount: Uint256| {
+            Uint128::try_from(fee_amount).map_err(|_| StdError::generic_err("Fee conversion error"))
+        };
 
-        // Compute swap fee
-        let swap_fee_amount = self.swap_fee.compute(amount)?;
-        total_fee_amount = total_fee_amount.checked_add(swap_fee_amount)?;
+        Ok(FeeBreakdown {
+            protocol_fee_amount: to_uint128(self.protocol_fee.compute(amount)?)?,
+            swap_fee_amount: to_uint128(self.swap_fee.compute(amount)?)?,
+            burn_fee_amount: to_uint128(self.burn_fee.compute(amount)?)?,
+            extra_fee_amounts: self
+                .extra_fees
+                .iter()
+                .map(|extra_fee| to_uint128(extra_fee.compute(amount)?))
+                .collect::<StdResult<_>>()?,
+        })
+    }
 
-        // Compute burn fee
-        let burn_fee_amount = self.burn_fee.compute(amount)?;
-        total_fee_amount = total_fee_amount.checked_add(burn_fee_amount)?;
+    /// Gets the sum of all the fee shares of the pool.
+    pub fn total_share(&self) -> StdResult<Decimal256> {
+        [&self.protocol_fee, &self.swap_fee, &self.burn_fee]
+            .into_iter()
+            .chain(&self.extra_fees)
+            .try_fold(Decimal256::zero(), |acc, fee| {
+                Ok(acc.checked_add(fee.to_decimal_256())?)
+            })
+    }
 
-        // Compute extra fees
-        for extra_fee in &self.extra_fees {
-            let extra_fee_amount = extra_fee.compute(amount)?;
-            total_fee_amount = total_fee_amount.checked_add(extra_fee_amount)?;
+    /// Computes the smallest gross amount that is left with at least `net_amount` after the fees
+    /// computed by [PoolFee::compute_fee_breakdown] are deducted from it.
+    pub fn compute_gross_amount(&self, net_amount: Uint256) -> StdResult<Uint256> {
+        let one_minus_fees = Decimal256::one()
+            .checked_sub(self.total_share()?)
+            .ok()
+            .filter(|share| !share.is_zero())
+            .ok_or_else(|| StdError::generic_err("Total fees must be lower than 100%"))?;
+
+        // the fees are rounded down one by one, so each of them can leave up to one unit more
+        // than the exact net amount. The gross amount is therefore between the exact gross amount
+        // of `net_amount - fee_count` and the exact gross amount of `net_amount`, rounded up.
+        let fee_count = Uint256::from(3u128 + self.extra_fees.len() as u128);
+        let lower_bound = Decimal256::from_ratio(net_amount.saturating_sub(fee_count), 1u8)
+            .checked_div(one_minus_fees)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+            .to_uint_floor();
+        let upper_bound = Decimal256::from_ratio(net_amount, 1u8)
+            .checked_div(one_minus_fees)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+            .to_uint_ceil();
+
+        let mut gross_amount = lower_bound;
+        while gross_amount < upper_bound {
+            let fees = Uint256::from(self.compute_and_apply_fees(gross_amount)?);
+            if gross_amount.checked_sub(fees)? >= net_amount {
+                return Ok(gross_amount);
+            }
+            gross_amount = gross_amount.checked_add(Uint256::one())?;
         }
 
-        // Convert the total fee amount to Uint128 (or handle potential conversion failure)
-        Uint128::try_from(total_fee_amount)
-            .map_err(|_| StdError::generic_err("Fee conversion error"))
+        Ok(upper_bound)
+    }
+}
+
+/// The amount charged by each of the fees of a pool, see [PoolFee::compute_fee_breakdown].
+#[cw_serde]
+pub struct FeeBreakdown {
+    /// The amount charged by the protocol fee.
+    pub protocol_fee_amount: Uint128,
+    /// The amount charged by the swap fee.
+    pub swap_fee_amount: Uint128,
+    /// The amount charged by the burn fee.
+    pub burn_fee_amount: Uint128,
+    /// The amount charged by each of the extra fees, in the same order as
+    /// [PoolFee::extra_fees].
+    pub extra_fee_amounts: Vec<Uint128>,
+}
+
+impl FeeBreakdown {
+    /// Gets the sum of the extra fee amounts.
+    pub fn extra_fees_amount(&self) -> StdResult<Uint128> {
+        self.extra_fee_amounts
+            .iter()
+            .try_fold(Uint128::zero(), |acc, amount| Ok(acc.checked_add(*amount)?))
+    }
+
+    /// Gets the sum of all the fee amounts.
+    pub fn total(&self) -> StdResult<Uint128> {
+        Ok(self
+            .protocol_fee_amount
+            .checked_add(self.swap_fee_amount)?
+            .checked_add(self.burn_fee_amount)?
+            .checked_add(self.extra_fees_amount()?)?)
+    }
+}
+
+/// A fee tier, giving traders that reached a minimum trading volume a discount on the pool fees.
+#[cw_serde]
+pub struct FeeTier {
+    /// The minimum trading volume to qualify for the tier.
+    pub min_volume: Uint128,
+    /// The discount applied to every fee of the pool, i.e. a discount of 10% turns a 1% fee into
+    /// a 0.9% fee.
+    pub discount: Decimal,
+}
+
+/// A rebate on the protocol fee for a specific address, e.g. a market maker.
+#[cw_serde]
+pub struct ProtocolFeeRebate {
+    /// The address getting the rebate.
+    pub address: String,
+    /// The share of the protocol fee that is rebated, applied on top of the tier discount.
+    pub rebate: Decimal,
+}
+
+/// The fee tiers and protocol fee rebates applied on top of the pool fees.
+#[cw_serde]
+#[derive(Default)]
+pub struct FeeTierSchedule {
+    /// The fee tiers, sorted by ascending minimum volume.
+    pub tiers: Vec<FeeTier>,
+    /// The protocol fee rebates per address.
+    pub rebates: Vec<ProtocolFeeRebate>,
+}
+
+impl FeeTierSchedule {
+    /// Validates the schedule. Tiers must be sorted by strictly ascending minimum volume and
+    /// discounts and rebates cannot exceed 100%.
+    pub fn validate(&self) -> StdResult<()> {
+        for tier in &self.tiers {
+            if tier.discount > Decimal::one() {
+                return Err(StdError::generic_err(
+                    "Fee tier discounts cannot exceed 100%",
+                ));
+            }
+        }
+
+        if self
+            .tiers
+            .windows(2)
+            .any(|tiers| tiers[0].min_volume >= tiers[1].min_volume)
+        {
+            return Err(StdError::generic_err(
+                "Fee tiers must be sorted by ascending minimum volume",
+            ));
+        }
+
+        for (i, rebate) in self.rebates.iter().enumerate() {
+            if rebate.rebate > Decimal::one() {
+                return Err(StdError::generic_err(
+                    "Protocol fee rebates cannot exceed 100%",
+                ));
+            }
+            if self.rebates[..i]
+                .iter()
+                .any(|r| r.address == rebate.address)
+            {
+                return Err(StdError::generic_err(format!(
+                    "Duplicated protocol fee rebate for {}",
+                    rebate.address
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the highest tier the given trading volume qualifies for, if any.
+    pub fn get_tier(&self, volume: Uint128) -> Option<&FeeTier> {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_volume <= volume)
+    }
+
+    /// Resolves the effective fees of a pool for the given trader. The tier discount is applied
+    /// to every fee, and the trader's protocol fee rebate, if any, to the protocol fee. The
+    /// effective fees are never higher than the pool fees, so [PoolFee::compute_and_apply_fees]
+    /// never charges more than it would without the schedule.
+    pub fn resolve_pool_fee(
+        &self,
+        pool_fee: &PoolFee,
+        address: &str,
+        volume: Uint128,
+    ) -> StdResult<PoolFee> {
+        let discount = self
+            .get_tier(volume)
+            .map(|tier| tier.discount)
+            .unwrap_or_default();
+        let rebate = self
+            .rebates
+            .iter()
+            .find(|rebate| rebate.address == address)
+            .map(|rebate| rebate.rebate)
+            .unwrap_or_default();
+
+        let discounted = |fee: &Fee| -> StdResult<Fee> {
+            Ok(Fee {
+                share: fee
+                    .share
+                    .checked_mul(Decimal::one().checked_sub(discount)?)?,
+            })
+        };
+
+        let protocol_fee = discounted(&pool_fee.protocol_fee)?;
+
+        Ok(PoolFee {
+            protocol_fee: Fee {
+                share: protocol_fee
+                    .share
+                    .checked_mul(Decimal::one().checked_sub(rebate)?)?,
+            },
+            swap_fee: discounted(&pool_fee.swap_fee)?,
+            burn_fee: discounted(&pool_fee.burn_fee)?,
+            extra_fees: pool_fee
+                .extra_fees
+                .iter()
+                .map(discounted)
+                .collect::<StdResult<_>>()?,
+        })
+    }
+}
+
+/// A swap fee that rises as the pool becomes imbalanced, meant for stableswap pools of pegged
+/// assets. The effective fee is `base_fee + imbalance_sensitivity * imbalance`, capped at
+/// `max_fee`, where the imbalance is `1 - min_reserve / max_reserve` with the reserves normalized
+/// to the same precision. A balanced pool charges the base fee.
+#[cw_serde]
+pub struct DynamicFee {
+    /// The fee charged when the pool is balanced.
+    pub base_fee: Fee,
+    /// The maximum fee charged, however imbalanced the pool is.
+    pub max_fee: Fee,
+    /// How much the fee rises per unit of imbalance.
+    pub imbalance_sensitivity: Decimal,
+}
+
+impl DynamicFee {
+    /// Validates the dynamic fee for the given pool fees, whose `swap_fee` it replaces. The pool
+    /// fees must be valid with the swap fee at its maximum, i.e. not exceed `max_total_fee`, see
+    /// [PoolFee::is_valid_with_max_total_fee].
+    pub fn is_valid(&self, pool_fee: &PoolFee, max_total_fee: Decimal) -> StdResult<()> {
+        self.base_fee.is_valid()?;
+        self.max_fee.is_valid()?;
+
+        if self.base_fee.share > self.max_fee.share {
+            return Err(StdError::generic_err(
+                "The base fee cannot be higher than the max fee",
+            ));
+        }
+
+        PoolFee {
+            swap_fee: self.max_fee.clone(),
+            ..pool_fee.clone()
+        }
+        .is_valid_with_max_total_fee(max_total_fee)
+    }
+
+    /// Computes the effective fee for a pool with the given assets and decimals.
+    pub fn compute_fee(&self, assets: &[Coin], asset_decimals: &[u8]) -> StdResult<Fee> {
+        if assets.len() != asset_decimals.len() {
+            return Err(StdError::generic_err(
+                "The assets and asset decimals don't match",
+            ));
+        }
+        if assets.len() < 2 {
+            return Err(StdError::generic_err(
+                "A dynamic fee needs at least two assets",
+            ));
+        }
+
+        let reserves = assets
+            .iter()
+            .zip(asset_decimals)
+            .map(|(asset, decimals)| {
+                let decimals = 18u32.checked_sub(u32::from(*decimals)).ok_or_else(|| {
+                    StdError::generic_err(format!("Invalid decimals for {}", asset.denom))
+                })?;
+                Ok(Uint256::from(asset.amount).checked_mul(Uint256::from(10u128.pow(decimals)))?)
+            })
+            .collect::<StdResult<Vec<Uint256>>>()?;
+
+        let min_reserve = reserves.iter().min().copied().unwrap_or_default();
+        let max_reserve = reserves.iter().max().copied().unwrap_or_default();
+        if max_reserve.is_zero() {
+            return Ok(self.base_fee.clone());
+        }
+
+        let imbalance =
+            Decimal256::one().checked_sub(Decimal256::from_ratio(min_reserve, max_reserve))?;
+        let fee_share = Decimal256::from(self.base_fee.share)
+            .checked_add(imbalance.checked_mul(Decimal256::from(self.imbalance_sensitivity))?)?;
+
+        Ok(Fee {
+            share: Decimal::try_from(fee_share.min(Decimal256::from(self.max_fee.share)))
+                .map_err(|e| StdError::generic_err(e.to_string()))?,
+        })
+    }
+
+    /// Gets the pool fees with the swap fee replaced by the effective dynamic fee.
+    pub fn apply(
+        &self,
+        pool_fee: &PoolFee,
+        assets: &[Coin],
+        asset_decimals: &[u8],
+    ) -> StdResult<PoolFee> {
+        Ok(PoolFee {
+            swap_fee: self.compute_fee(assets, asset_decimals)?,
+            ..pool_fee.clone()
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{Decimal, StdError, Uint128, Uint256};
+    use cosmwasm_std::{coin, Decimal, StdError, Uint128, Uint256};
     use test_case::test_case;
 
-    use crate::fee::{Fee, PoolFee};
+    use crate::fee::{
+        DynamicFee, Fee, FeeBreakdown, FeeTier, FeeTierSchedule, PoolFee, ProtocolFeeRebate,
+        DEFAULT_MAX_TOTAL_FEE,
+    };
 
     #[test]
     fn valid_fee() {
@@ -240,4 +551,326 @@ mod tests {
             Err(StdError::generic_err("Total fees cannot exceed 20%"))
         );
     }
+
+    #[test]
+    fn pool_fee_with_max_total_fee() {
+        let pool_fee = PoolFee {
+            protocol_fee: Fee {
+                share: Decimal::percent(10),
+            },
+            swap_fee: Fee {
+                share: Decimal::percent(15),
+            },
+            burn_fee: Fee {
+                share: Decimal::zero(),
+            },
+            extra_fees: vec![],
+        };
+
+        assert_eq!(
+            pool_fee.is_valid(),
+            Err(StdError::generic_err("Total fees cannot exceed 20%"))
+        );
+        assert!(pool_fee
+            .is_valid_with_max_total_fee(Decimal::percent(25))
+            .is_ok());
+        assert_eq!(
+            pool_fee.is_valid_with_max_total_fee(Decimal::permille(245)),
+            Err(StdError::generic_err("Total fees cannot exceed 24.5%"))
+        );
+        assert_eq!(
+            pool_fee.is_valid_with_max_total_fee(Decimal::one()),
+            Err(StdError::generic_err("Invalid maximum total fee"))
+        );
+    }
+
+    #[test]
+    fn resolve_fee_tiers() {
+        let schedule = FeeTierSchedule {
+            tiers: vec![
+                FeeTier {
+                    min_volume: Uint128::new(1_000),
+                    discount: Decimal::percent(10),
+                },
+                FeeTier {
+                    min_volume: Uint128::new(10_000),
+                    discount: Decimal::percent(50),
+                },
+            ],
+            rebates: vec![ProtocolFeeRebate {
+                address: "market_maker".to_string(),
+                rebate: Decimal::percent(100),
+            }],
+        };
+        schedule.validate().unwrap();
+
+        let pool_fee = PoolFee {
+            protocol_fee: Fee {
+                share: Decimal::permille(2),
+            },
+            swap_fee: Fee {
+                share: Decimal::permille(4),
+            },
+            burn_fee: Fee {
+                share: Decimal::zero(),
+            },
+            extra_fees: vec![Fee {
+                share: Decimal::permille(2),
+            }],
+        };
+
+        // no tier, the fees stay the same
+        let resolved = schedule
+            .resolve_pool_fee(&pool_fee, "trader", Uint128::new(999))
+            .unwrap();
+        assert_eq!(resolved, pool_fee);
+        assert_eq!(
+            resolved
+                .compute_and_apply_fees(Uint256::from(10_000u128))
+                .unwrap(),
+            pool_fee
+                .compute_and_apply_fees(Uint256::from(10_000u128))
+                .unwrap()
+        );
+
+        let resolved = schedule
+            .resolve_pool_fee(&pool_fee, "trader", Uint128::new(10_000))
+            .unwrap();
+        assert_eq!(resolved.swap_fee.share, Decimal::permille(2));
+        assert_eq!(resolved.extra_fees[0].share, Decimal::permille(1));
+        assert_eq!(
+            resolved
+                .compute_and_apply_fees(Uint256::from(10_000u128))
+                .unwrap(),
+            Uint128::new(40)
+        );
+
+        let resolved = schedule
+            .resolve_pool_fee(&pool_fee, "market_maker", Uint128::new(5_000))
+            .unwrap();
+        assert_eq!(resolved.protocol_fee.share, Decimal::zero());
+        assert_eq!(
+            resolved.swap_fee.share,
+            Decimal::permille(4) * Decimal::percent(90)
+        );
+    }
+
+    #[test]
+    fn invalid_fee_tier_schedule() {
+        let schedule = FeeTierSchedule {
+            tiers: vec![
+                FeeTier {
+                    min_volume: Uint128::new(1_000),
+                    discount: Decimal::percent(10),
+                },
+                FeeTier {
+                    min_volume: Uint128::new(1_000),
+                    discount: Decimal::percent(20),
+                },
+            ],
+            rebates: vec![],
+        };
+        assert_eq!(
+            schedule.validate(),
+            Err(StdError::generic_err(
+                "Fee tiers must be sorted by ascending minimum volume"
+            ))
+        );
+
+        let schedule = FeeTierSchedule {
+            tiers: vec![],
+            rebates: vec![ProtocolFeeRebate {
+                address: "market_maker".to_string(),
+                rebate: Decimal::percent(101),
+            }],
+        };
+        assert_eq!(
+            schedule.validate(),
+            Err(StdError::generic_err(
+                "Protocol fee rebates cannot exceed 100%"
+            ))
+        );
+    }
+
+    fn pool_fee_with_extra_fees() -> PoolFee {
+        PoolFee {
+            protocol_fee: Fee {
+                share: Decimal::permille(1),
+            },
+            swap_fee: Fee {
+                share: Decimal::permille(3),
+            },
+            burn_fee: Fee {
+                share: Decimal::zero(),
+            },
+            extra_fees: vec![
+                Fee {
+                    share: Decimal::permille(2),
+                },
+                Fee {
+                    share: Decimal::permille(5),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn pool_fee_breakdown() {
+        let pool_fee = pool_fee_with_extra_fees();
+
+        let breakdown = pool_fee
+            .compute_fee_breakdown(Uint256::from(1_999u128))
+            .unwrap();
+        assert_eq!(
+            breakdown,
+            FeeBreakdown {
+                protocol_fee_amount: Uint128::new(1),
+                swap_fee_amount: Uint128::new(5),
+                burn_fee_amount: Uint128::zero(),
+                extra_fee_amounts: vec![Uint128::new(3), Uint128::new(9)],
+            }
+        );
+        assert_eq!(breakdown.extra_fees_amount().unwrap(), Uint128::new(12));
+        assert_eq!(
+            breakdown.total().unwrap(),
+            pool_fee
+                .compute_and_apply_fees(Uint256::from(1_999u128))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn pool_fee_gross_amount() {
+        let pool_fee = pool_fee_with_extra_fees();
+
+        for net_amount in [0u128, 1, 7, 989, 1_000, 123_456, 10_000_000] {
+            let net_amount = Uint256::from(net_amount);
+            let gross_amount = pool_fee.compute_gross_amount(net_amount).unwrap();
+
+            let net_of = |gross: Uint256| {
+                gross - Uint256::from(pool_fee.compute_and_apply_fees(gross).unwrap())
+            };
+            assert!(net_of(gross_amount) >= net_amount);
+            if !gross_amount.is_zero() {
+                assert!(net_of(gross_amount - Uint256::one()) < net_amount);
+            }
+        }
+
+        let pool_fee = PoolFee {
+            protocol_fee: Fee {
+                share: Decimal::percent(50),
+            },
+            swap_fee: Fee {
+                share: Decimal::percent(50),
+            },
+            burn_fee: Fee {
+                share: Decimal::zero(),
+            },
+            extra_fees: vec![],
+        };
+        assert_eq!(
+            pool_fee.compute_gross_amount(Uint256::one()),
+            Err(StdError::generic_err("Total fees must be lower than 100%"))
+        );
+    }
+
+    fn dynamic_fee() -> DynamicFee {
+        DynamicFee {
+            base_fee: Fee {
+                share: Decimal::permille(1),
+            },
+            max_fee: Fee {
+                share: Decimal::percent(1),
+            },
+            imbalance_sensitivity: Decimal::percent(5),
+        }
+    }
+
+    #[test]
+    fn dynamic_fee_rises_with_imbalance() {
+        let dynamic_fee = dynamic_fee();
+
+        // balanced pool with different decimals
+        assert_eq!(
+            dynamic_fee
+                .compute_fee(
+                    &[
+                        coin(1_000_000, "uusdc"),
+                        coin(1_000_000_000_000_000_000, "aeur")
+                    ],
+                    &[6, 18]
+                )
+                .unwrap()
+                .share,
+            Decimal::permille(1)
+        );
+        // 10% imbalance, 0.1% + 0.5%
+        assert_eq!(
+            dynamic_fee
+                .compute_fee(&[coin(900, "uusdc"), coin(1_000, "ueur")], &[6, 6])
+                .unwrap()
+                .share,
+            Decimal::permille(6)
+        );
+        // capped at the max fee
+        assert_eq!(
+            dynamic_fee
+                .compute_fee(&[coin(100, "uusdc"), coin(1_000, "ueur")], &[6, 6])
+                .unwrap()
+                .share,
+            Decimal::percent(1)
+        );
+        assert_eq!(
+            dynamic_fee.compute_fee(&[coin(100, "uusdc"), coin(1_000, "ueur")], &[6]),
+            Err(StdError::generic_err(
+                "The assets and asset decimals don't match"
+            ))
+        );
+        assert_eq!(
+            dynamic_fee.compute_fee(&[coin(100, "uusdc")], &[6]),
+            Err(StdError::generic_err(
+                "A dynamic fee needs at least two assets"
+            ))
+        );
+    }
+
+    #[test]
+    fn dynamic_fee_validation() {
+        let pool_fee = pool_fee_with_extra_fees();
+        assert!(dynamic_fee()
+            .is_valid(&pool_fee, DEFAULT_MAX_TOTAL_FEE)
+            .is_ok());
+
+        let mut invalid_dynamic_fee = dynamic_fee();
+        invalid_dynamic_fee.base_fee.share = Decimal::percent(2);
+        assert_eq!(
+            invalid_dynamic_fee.is_valid(&pool_fee, DEFAULT_MAX_TOTAL_FEE),
+            Err(StdError::generic_err(
+                "The base fee cannot be higher than the max fee"
+            ))
+        );
+
+        invalid_dynamic_fee.max_fee.share = Decimal::percent(20);
+        assert_eq!(
+            invalid_dynamic_fee.is_valid(&pool_fee, DEFAULT_MAX_TOTAL_FEE),
+            Err(StdError::generic_err("Total fees cannot exceed 20%"))
+        );
+        // the cap is configurable
+        assert!(invalid_dynamic_fee
+            .is_valid(&pool_fee, Decimal::percent(25))
+            .is_ok());
+        assert!(dynamic_fee()
+            .is_valid(&pool_fee, Decimal::percent(1))
+            .is_err());
+
+        let applied = dynamic_fee()
+            .apply(
+                &pool_fee,
+                &[coin(900, "uusdc"), coin(1_000, "ueur")],
+                &[6, 6],
+            )
+            .unwrap();
+        assert_eq!(applied.swap_fee.share, Decimal::permille(6));
+        assert_eq!(applied.extra_fees, pool_fee.extra_fees);
+    }
 }
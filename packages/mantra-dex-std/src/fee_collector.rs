@@ -1,17 +1,317 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{ensure, Addr, Coin, Decimal, StdError, StdResult, Timestamp, Uint128};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
+use crate::pool_manager::SwapOperation;
+
+/// The instantiation message
 #[cw_serde]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+    /// The pool manager address, used to swap the collected fees.
+    pub pool_manager_addr: String,
+    /// The denom the collected fees are swapped into before being distributed.
+    pub target_denom: String,
+    /// The recipients of the distributed fees.
+    pub recipients: Vec<FeeRecipient>,
+}
 
+/// The execution messages
 #[cw_ownable_execute]
 #[cw_serde]
-pub enum ExecuteMsg {}
+pub enum ExecuteMsg {
+    /// Collects the fees sent along with the message, i.e. the protocol fees from the pool
+    /// manager and the farm creation fees from the farm manager.
+    Collect {},
+    /// Swaps the accumulated fees into the target denom, through the pool manager. Only callable
+    /// by the owner.
+    SwapFees {
+        /// The swaps to perform.
+        swaps: Vec<FeeSwap>,
+    },
+    /// Distributes the accumulated fees of the target denom to the recipients, according to their
+    /// shares.
+    Distribute {},
+    /// Updates the config of the contract.
+    UpdateConfig {
+        /// The pool manager address, used to swap the collected fees.
+        pool_manager_addr: Option<String>,
+        /// The denom the collected fees are swapped into before being distributed.
+        target_denom: Option<String>,
+        /// The recipients of the distributed fees.
+        recipients: Option<Vec<FeeRecipient>>,
+    },
+}
 
+/// The query messages
 #[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum QueryMsg {}
+pub enum QueryMsg {
+    /// Retrieves the configuration of the contract.
+    #[returns(Config)]
+    Config {},
+    /// Retrieves the fees accumulated in the contract that haven't been distributed yet.
+    #[returns(AccumulatedFeesResponse)]
+    AccumulatedFees {},
+    /// Retrieves the past distributions, from the most recent to the oldest.
+    #[returns(DistributionsResponse)]
+    Distributions {
+        /// The distribution id to start querying from.
+        start_after: Option<u64>,
+        /// The number of distributions to return.
+        limit: Option<u32>,
+    },
+}
 
+/// The migrate message
 #[cw_serde]
 pub enum MigrateMsg {}
+
+/// Configuration for the contract (fee collector)
+#[cw_serde]
+pub struct Config {
+    /// The pool manager address, used to swap the collected fees.
+    pub pool_manager_addr: Addr,
+    /// The denom the collected fees are swapped into before being distributed.
+    pub target_denom: String,
+    /// The recipients of the distributed fees.
+    pub recipients: Vec<FeeRecipient>,
+}
+
+/// A recipient of the fees distributed by the fee collector.
+#[cw_serde]
+pub struct FeeRecipient {
+    /// The address of the recipient.
+    pub address: String,
+    /// The share of the distributed fees the recipient gets.
+    pub share: Decimal,
+}
+
+/// A swap of accumulated fees into the target denom.
+#[cw_serde]
+pub struct FeeSwap {
+    /// The fees to swap. Must be part of the accumulated fees.
+    pub offer_asset: Coin,
+    /// The route to swap through, which must end in the target denom.
+    pub operations: Vec<SwapOperation>,
+    /// The minimum amount of the target denom to receive from the swap.
+    pub minimum_receive: Option<Uint128>,
+    /// The maximum allowable slippage of each swap of the route.
+    pub max_slippage: Option<Decimal>,
+}
+
+impl FeeSwap {
+    /// Validates the swap route goes from the offered asset into the target denom.
+    pub fn validate(&self, target_denom: &str) -> StdResult<()> {
+        ensure!(
+            !self.offer_asset.amount.is_zero(),
+            StdError::generic_err("Cannot swap a zero amount of fees")
+        );
+
+        let (first, last) = self
+            .operations
+            .first()
+            .zip(self.operations.last())
+            .ok_or_else(|| StdError::generic_err("The fee swap has no operations"))?;
+        ensure!(
+            *first.get_input_asset_info() == self.offer_asset.denom,
+            StdError::generic_err(format!(
+                "The fee swap route must start with {}",
+                self.offer_asset.denom
+            ))
+        );
+        ensure!(
+            last.get_target_asset_info() == target_denom,
+            StdError::generic_err(format!("The fee swap route must end with {target_denom}"))
+        );
+        ensure!(
+            self.operations
+                .windows(2)
+                .all(|ops| ops[0].get_target_asset_info() == *ops[1].get_input_asset_info()),
+            StdError::generic_err("The fee swap operations are not consecutive")
+        );
+
+        Ok(())
+    }
+}
+
+/// Validates the fee recipients, whose shares must add up to one.
+pub fn validate_recipients(recipients: &[FeeRecipient]) -> StdResult<()> {
+    ensure!(
+        !recipients.is_empty(),
+        StdError::generic_err("There must be at least one fee recipient")
+    );
+
+    let mut total_share = Decimal::zero();
+    for (i, recipient) in recipients.iter().enumerate() {
+        ensure!(
+            !recipient.share.is_zero(),
+            StdError::generic_err(format!(
+                "The share of fee recipient {} cannot be zero",
+                recipient.address
+            ))
+        );
+        ensure!(
+            !recipients[..i]
+                .iter()
+                .any(|other| other.address == recipient.address),
+            StdError::generic_err(format!("Fee recipient {} is duplicated", recipient.address))
+        );
+        total_share = total_share.checked_add(recipient.share)?;
+    }
+
+    ensure!(
+        total_share == Decimal::one(),
+        StdError::generic_err(format!(
+            "Fee recipient shares must add up to one, got {total_share}"
+        ))
+    );
+
+    Ok(())
+}
+
+/// Splits the given fees among the recipients according to their shares. Each share is rounded
+/// down, and the remainder goes to the first recipient so the whole amount is distributed.
+pub fn compute_distribution(
+    fees: &Coin,
+    recipients: &[FeeRecipient],
+) -> StdResult<Vec<DistributedFee>> {
+    validate_recipients(recipients)?;
+
+    let mut distributed_fees = recipients
+        .iter()
+        .map(|recipient| DistributedFee {
+            recipient: recipient.address.clone(),
+            amount: Coin {
+                denom: fees.denom.clone(),
+                amount: fees.amount.mul_floor(recipient.share),
+            },
+        })
+        .collect::<Vec<_>>();
+
+    let distributed_amount = distributed_fees
+        .iter()
+        .try_fold(Uint128::zero(), |total, fee| {
+            total.checked_add(fee.amount.amount)
+        })?;
+    let remainder = fees.amount.checked_sub(distributed_amount)?;
+    distributed_fees[0].amount.amount = distributed_fees[0].amount.amount.checked_add(remainder)?;
+
+    Ok(distributed_fees)
+}
+
+/// The response for the accumulated fees query
+#[cw_serde]
+pub struct AccumulatedFeesResponse {
+    /// The fees accumulated in the contract.
+    pub fees: Vec<Coin>,
+}
+
+/// The fees a recipient got on a distribution.
+#[cw_serde]
+pub struct DistributedFee {
+    /// The address of the recipient.
+    pub recipient: String,
+    /// The fees distributed to the recipient.
+    pub amount: Coin,
+}
+
+/// A distribution of the accumulated fees.
+#[cw_serde]
+pub struct Distribution {
+    /// The id of the distribution.
+    pub id: u64,
+    /// The time the distribution took place.
+    pub timestamp: Timestamp,
+    /// The total fees distributed.
+    pub total: Coin,
+    /// The fees distributed to each recipient.
+    pub distributed_fees: Vec<DistributedFee>,
+}
+
+/// The response for the distributions query
+#[cw_serde]
+pub struct DistributionsResponse {
+    /// The distributions.
+    pub distributions: Vec<Distribution>,
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Decimal, StdError};
+
+    use crate::fee_collector::{compute_distribution, validate_recipients, FeeRecipient, FeeSwap};
+    use crate::pool_manager::SwapOperation;
+
+    fn recipient(address: &str, share: u64) -> FeeRecipient {
+        FeeRecipient {
+            address: address.to_string(),
+            share: Decimal::percent(share),
+        }
+    }
+
+    fn operation(token_in_denom: &str, token_out_denom: &str) -> SwapOperation {
+        SwapOperation::MantraSwap {
+            token_in_denom: token_in_denom.to_string(),
+            token_out_denom: token_out_denom.to_string(),
+            pool_identifier: format!("{token_in_denom}.{token_out_denom}"),
+        }
+    }
+
+    #[test]
+    fn distribute_fees() {
+        let recipients = vec![recipient("treasury", 50), recipient("stakers", 50)];
+
+        let distribution = compute_distribution(&coin(1_001, "uom"), &recipients).unwrap();
+        assert_eq!(distribution[0].amount, coin(501, "uom"));
+        assert_eq!(distribution[1].amount, coin(500, "uom"));
+    }
+
+    #[test]
+    fn invalid_recipients() {
+        assert_eq!(
+            validate_recipients(&[recipient("treasury", 50), recipient("stakers", 40)]),
+            Err(StdError::generic_err(
+                "Fee recipient shares must add up to one, got 0.9"
+            ))
+        );
+        assert_eq!(
+            validate_recipients(&[recipient("treasury", 50), recipient("treasury", 50)]),
+            Err(StdError::generic_err(
+                "Fee recipient treasury is duplicated"
+            ))
+        );
+        assert!(validate_recipients(&[]).is_err());
+        assert!(
+            validate_recipients(&[recipient("treasury", 100), recipient("stakers", 0)]).is_err()
+        );
+    }
+
+    #[test]
+    fn validate_fee_swap() {
+        let swap = FeeSwap {
+            offer_asset: coin(1_000, "uatom"),
+            operations: vec![operation("uatom", "uusdc"), operation("uusdc", "uom")],
+            minimum_receive: None,
+            max_slippage: None,
+        };
+        assert!(swap.validate("uom").is_ok());
+        assert_eq!(
+            swap.validate("uusdc"),
+            Err(StdError::generic_err(
+                "The fee swap route must end with uusdc"
+            ))
+        );
+
+        let swap = FeeSwap {
+            operations: vec![operation("uatom", "uusdc"), operation("uosmo", "uom")],
+            ..swap
+        };
+        assert_eq!(
+            swap.validate("uom"),
+            Err(StdError::generic_err(
+                "The fee swap operations are not consecutive"
+            ))
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    ensure, to_json_binary, Binary, Coin, CosmosMsg, StdError, StdResult, Uint128, Uint256, WasmMsg,
+};
+
+use crate::fee::{FeeBreakdown, PoolFee};
+
+/// The message the pool manager sends to the borrower of a flash swap, once the borrowed asset
+/// has been sent to it.
+#[cw_serde]
+pub struct FlashSwapCallbackMsg {
+    /// The identifier of the pool the asset was borrowed from.
+    pub pool_identifier: String,
+    /// The borrowed asset.
+    pub borrowed: Coin,
+    /// The asset that must be sent back to the pool manager before the transaction ends.
+    pub repayment: Coin,
+    /// The message passed in the `FlashSwap` message.
+    pub msg: Binary,
+}
+
+impl FlashSwapCallbackMsg {
+    /// Serializes the message wrapped in a [FlashSwapReceiverMsg].
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        to_json_binary(&FlashSwapReceiverMsg::FlashSwapCallback(self))
+    }
+
+    /// Creates the message to call the borrower contract with.
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: self.into_json_binary()?,
+            funds: vec![],
+        }))
+    }
+}
+
+/// The execute message flash swap borrowers must implement, i.e. as a variant of their own
+/// `ExecuteMsg`.
+#[cw_serde]
+pub enum FlashSwapReceiverMsg {
+    /// Called by the pool manager after sending the borrowed asset.
+    FlashSwapCallback(FlashSwapCallbackMsg),
+}
+
+/// Computes the asset to repay for borrowing `amount` from a pool with the given fees. The
+/// repayment is the smallest amount that is left with at least the borrowed amount after the
+/// pool fees are deducted from it, i.e. [PoolFee::compute_gross_amount].
+pub fn compute_flash_swap_repayment(pool_fees: &PoolFee, amount: &Coin) -> StdResult<Coin> {
+    ensure!(
+        !amount.amount.is_zero(),
+        StdError::generic_err("Cannot flash swap a zero amount")
+    );
+
+    Ok(Coin {
+        denom: amount.denom.clone(),
+        amount: Uint128::try_from(pool_fees.compute_gross_amount(Uint256::from(amount.amount))?)?,
+    })
+}
+
+/// Ensures the flash swap was repaid, given the pool manager's balance of the borrowed asset
+/// before lending it and after the callback. Returns the fees charged on the repayment, which
+/// the pool manager handles like swap fees.
+pub fn assert_flash_swap_repaid(
+    pool_fees: &PoolFee,
+    borrowed: &Coin,
+    balance_before: Uint128,
+    balance_after: Uint128,
+) -> StdResult<FeeBreakdown> {
+    let repayment = compute_flash_swap_repayment(pool_fees, borrowed)?;
+    let expected_balance = balance_before
+        .checked_sub(borrowed.amount)?
+        .checked_add(repayment.amount)?;
+
+    ensure!(
+        balance_after >= expected_balance,
+        StdError::generic_err(format!(
+            "The flash swap was not repaid, expected a repayment of {repayment}"
+        ))
+    );
+
+    pool_fees.compute_fee_breakdown(Uint256::from(repayment.amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, from_json, Binary, Decimal, StdError, Uint128, Uint256};
+
+    use crate::fee::{Fee, PoolFee};
+    use crate::flash_swap::{
+        assert_flash_swap_repaid, compute_flash_swap_repayment, FlashSwapCallbackMsg,
+        FlashSwapReceiverMsg,
+    };
+
+    fn pool_fees() -> PoolFee {
+        PoolFee {
+            protocol_fee: Fee {
+                share: Decimal::permille(1),
+            },
+            swap_fee: Fee {
+                share: Decimal::permille(2),
+            },
+            burn_fee: Fee {
+                share: Decimal::zero(),
+            },
+            extra_fees: vec![],
+        }
+    }
+
+    #[test]
+    fn flash_swap_repayment() {
+        let pool_fees = pool_fees();
+        let borrowed = coin(1_000_000, "uom");
+
+        let repayment = compute_flash_swap_repayment(&pool_fees, &borrowed).unwrap();
+        let fees = pool_fees
+            .compute_and_apply_fees(Uint256::from(repayment.amount))
+            .unwrap();
+        assert!(repayment.amount - fees >= borrowed.amount);
+        assert_eq!(repayment, coin(1_003_009, "uom"));
+
+        let breakdown = assert_flash_swap_repaid(
+            &pool_fees,
+            &borrowed,
+            Uint128::new(5_000_000),
+            Uint128::new(5_003_009),
+        )
+        .unwrap();
+        assert_eq!(breakdown.protocol_fee_amount, Uint128::new(1_003));
+
+        assert_eq!(
+            assert_flash_swap_repaid(
+                &pool_fees,
+                &borrowed,
+                Uint128::new(5_000_000),
+                Uint128::new(5_003_008),
+            ),
+            Err(StdError::generic_err(
+                "The flash swap was not repaid, expected a repayment of 1003009uom"
+            ))
+        );
+    }
+
+    #[test]
+    fn flash_swap_callback_msg() {
+        let msg = FlashSwapCallbackMsg {
+            pool_identifier: "om.usdc".to_string(),
+            borrowed: coin(1_000, "uom"),
+            repayment: coin(1_004, "uom"),
+            msg: Binary::default(),
+        };
+
+        let receiver_msg: FlashSwapReceiverMsg =
+            from_json(msg.clone().into_json_binary().unwrap()).unwrap();
+        assert_eq!(receiver_msg, FlashSwapReceiverMsg::FlashSwapCallback(msg));
+    }
+}
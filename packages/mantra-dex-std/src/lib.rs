@@ -2,13 +2,24 @@ pub mod coin;
 pub mod common;
 pub mod constants;
 pub mod epoch_manager;
+pub mod events;
 pub mod farm_manager;
+pub mod farm_rewards;
 pub mod fee;
 pub mod fee_collector;
+pub mod flash_swap;
+pub mod limit_order;
 pub mod lp_common;
 pub mod pool_manager;
+pub mod pool_manager_builder;
+pub mod pool_math;
+pub mod router;
 
 pub mod tokenfactory;
+pub mod twap;
+
+#[cfg(test)]
+mod testing;
 
 #[allow(clippy::all)]
 mod uints {
@@ -0,0 +1,200 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{ensure, Addr, Coin, Decimal, StdError, StdResult, Timestamp, Uint128};
+
+use crate::pool_manager::{PoolInfo, SimulationResponse};
+use crate::pool_math::simulate_swap;
+
+/// A resting order to swap the offer asset into the ask asset of a pool, once the pool price
+/// allows it.
+#[cw_serde]
+pub struct LimitOrder {
+    /// The id of the order.
+    pub id: u64,
+    /// The address that placed the order, and can cancel it.
+    pub owner: Addr,
+    /// The address that receives the swapped tokens.
+    pub receiver: Addr,
+    /// The identifier of the pool to swap in.
+    pub pool_identifier: String,
+    /// The asset to swap, held by the pool manager until the order is executed or cancelled.
+    pub offer_asset: Coin,
+    /// The return asset of the swap.
+    pub ask_asset_denom: String,
+    /// The minimum price, in ask assets per offer asset, the order is executed at.
+    pub trigger_price: Decimal,
+    /// The time the order was placed at.
+    pub created_at: Timestamp,
+    /// The time (unix timestamp), in seconds, after which the order can no longer be executed.
+    pub expires_at: Option<u64>,
+}
+
+/// The state of a limit order given the current pool state.
+#[cw_serde]
+pub enum LimitOrderStatus {
+    /// The pool price doesn't reach the trigger price yet.
+    Pending,
+    /// The order can be executed, with the given swap result.
+    Triggered {
+        /// The simulation of the swap that executes the order.
+        simulation: SimulationResponse,
+    },
+    /// The order expired and can only be cancelled.
+    Expired,
+}
+
+impl LimitOrder {
+    /// Validates the order parameters when it's placed at `block_time`.
+    pub fn validate(&self, block_time: &Timestamp) -> StdResult<()> {
+        ensure!(
+            !self.offer_asset.amount.is_zero(),
+            StdError::generic_err("The limit order offer amount cannot be zero")
+        );
+        ensure!(
+            self.offer_asset.denom != self.ask_asset_denom,
+            StdError::generic_err("The limit order must swap between different assets")
+        );
+        ensure!(
+            !self.trigger_price.is_zero(),
+            StdError::generic_err("The limit order trigger price cannot be zero")
+        );
+        ensure!(
+            self.expires_at
+                .is_none_or(|expires_at| expires_at > block_time.seconds()),
+            StdError::generic_err("The limit order expiration must be in the future")
+        );
+
+        Ok(())
+    }
+
+    /// Gets the minimum amount of the ask asset the order must return, i.e. the offer amount at
+    /// the trigger price.
+    pub fn minimum_receive(&self) -> Uint128 {
+        self.offer_asset.amount.mul_ceil(self.trigger_price)
+    }
+
+    /// Evaluates the order against the current state of its pool, at `block_time`. The order is
+    /// triggered when swapping the offer asset returns at least [LimitOrder::minimum_receive],
+    /// after fees and slippage.
+    pub fn evaluate(
+        &self,
+        pool_info: &PoolInfo,
+        block_time: &Timestamp,
+    ) -> StdResult<LimitOrderStatus> {
+        ensure!(
+            pool_info.pool_identifier == self.pool_identifier,
+            StdError::generic_err(format!(
+                "The limit order {} belongs to pool {}",
+                self.id, self.pool_identifier
+            ))
+        );
+
+        if self
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= block_time.seconds())
+        {
+            return Ok(LimitOrderStatus::Expired);
+        }
+
+        if !pool_info.status.swaps_enabled {
+            return Ok(LimitOrderStatus::Pending);
+        }
+
+        let simulation = simulate_swap(pool_info, &self.offer_asset, &self.ask_asset_denom)?;
+        if simulation.return_amount < self.minimum_receive() {
+            return Ok(LimitOrderStatus::Pending);
+        }
+
+        Ok(LimitOrderStatus::Triggered { simulation })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Addr, Decimal, StdError, Timestamp};
+
+    use crate::limit_order::{LimitOrder, LimitOrderStatus};
+    use crate::pool_manager::PoolType;
+    use crate::testing::om_usdc_pool;
+
+    fn order() -> LimitOrder {
+        LimitOrder {
+            id: 1,
+            owner: Addr::unchecked("owner"),
+            receiver: Addr::unchecked("owner"),
+            pool_identifier: "om.usdc".to_string(),
+            offer_asset: coin(1_000, "uom"),
+            ask_asset_denom: "uusdc".to_string(),
+            trigger_price: Decimal::percent(150),
+            created_at: Timestamp::from_seconds(100),
+            expires_at: Some(1_000),
+        }
+    }
+
+    #[test]
+    fn limit_order_triggers() {
+        let order = order();
+        let block_time = Timestamp::from_seconds(500);
+        assert_eq!(order.minimum_receive().u128(), 1_500);
+
+        // 1 uom = 1 uusdc
+        assert_eq!(
+            order
+                .evaluate(
+                    &om_usdc_pool(1_000_000, 1_000_000, PoolType::ConstantProduct),
+                    &block_time
+                )
+                .unwrap(),
+            LimitOrderStatus::Pending
+        );
+
+        // 1 uom = 2 uusdc
+        match order
+            .evaluate(
+                &om_usdc_pool(1_000_000, 2_000_000, PoolType::ConstantProduct),
+                &block_time,
+            )
+            .unwrap()
+        {
+            LimitOrderStatus::Triggered { simulation } => {
+                assert!(simulation.return_amount.u128() >= 1_500)
+            }
+            status => panic!("unexpected status {status:?}"),
+        }
+
+        assert_eq!(
+            order
+                .evaluate(
+                    &om_usdc_pool(1_000_000, 2_000_000, PoolType::ConstantProduct),
+                    &Timestamp::from_seconds(1_000)
+                )
+                .unwrap(),
+            LimitOrderStatus::Expired
+        );
+    }
+
+    #[test]
+    fn invalid_limit_orders() {
+        let block_time = Timestamp::from_seconds(100);
+        assert!(order().validate(&block_time).is_ok());
+
+        let mut invalid_order = order();
+        invalid_order.trigger_price = Decimal::zero();
+        assert_eq!(
+            invalid_order.validate(&block_time),
+            Err(StdError::generic_err(
+                "The limit order trigger price cannot be zero"
+            ))
+        );
+
+        assert_eq!(
+            order().validate(&Timestamp::from_seconds(1_000)),
+            Err(StdError::generic_err(
+                "The limit order expiration must be in the future"
+            ))
+        );
+
+        let mut other_pool = om_usdc_pool(1_000_000, 1_000_000, PoolType::ConstantProduct);
+        other_pool.pool_identifier = "om.atom".to_string();
+        assert!(order().evaluate(&other_pool, &block_time).is_err());
+    }
+}
@@ -1,6 +1,14 @@
 use crate::coin::is_factory_token;
+use crate::pool_manager::{PoolInfo, PoolType, SimulationResponse};
+use crate::pool_math::{
+    calculate_stableswap_d, decimal_with_precision, simulate_swap, to_uint256_with_precision,
+};
 use crate::tokenfactory;
-use cosmwasm_std::{ensure, Addr, Coin, CosmosMsg, StdError, StdResult, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    ensure, Addr, Coin, CosmosMsg, Decimal, Decimal256, Isqrt, StdError, StdResult, Uint128,
+    Uint256,
+};
 
 pub const MINIMUM_LIQUIDITY_AMOUNT: Uint128 = Uint128::new(1_000u128);
 
@@ -48,3 +56,426 @@ pub fn burn_lp_asset_msg(
         sender.into_string(),
     ))
 }
+
+/// Computes the LP shares minted for depositing `deposits` into the pool, mirroring the pool
+/// manager. `total_share` is the LP supply before the deposit, and the pool assets are the ones
+/// before the deposit as well.
+///
+/// On the first deposit, [MINIMUM_LIQUIDITY_AMOUNT] shares are locked in the pool manager and
+/// deducted from the returned shares.
+///
+/// Stableswap pools are only supported with 2 assets, as [calculate_stableswap_d] computes the
+/// invariant of 2 asset pools only.
+pub fn compute_lp_mint_amount(
+    pool_info: &PoolInfo,
+    total_share: Uint128,
+    deposits: &[Coin],
+) -> StdResult<Uint128> {
+    let deposits = get_deposit_amounts(pool_info, deposits)?;
+
+    let share = match &pool_info.pool_type {
+        PoolType::ConstantProduct => {
+            if total_share.is_zero() {
+                ensure!(
+                    pool_info.assets.len() == 2,
+                    StdError::generic_err(
+                        "Liquidity math is only supported for constant product pools with 2 assets"
+                    )
+                );
+
+                let product = deposits.iter().try_fold(Uint256::one(), |acc, deposit| {
+                    acc.checked_mul(Uint256::from(*deposit))
+                })?;
+                return initial_share(product.isqrt());
+            }
+
+            deposits
+                .iter()
+                .zip(&pool_info.assets)
+                .map(|(deposit, pool_asset)| {
+                    deposit
+                        .checked_multiply_ratio(total_share, pool_asset.amount)
+                        .map_err(|e| StdError::generic_err(e.to_string()))
+                })
+                .collect::<StdResult<Vec<_>>>()?
+                .into_iter()
+                .min()
+                .unwrap_or_default()
+        }
+        PoolType::StableSwap { amp } => {
+            ensure!(
+                pool_info.assets.len() == 2,
+                StdError::generic_err(
+                    "Liquidity math is only supported for stableswap pools with 2 assets"
+                )
+            );
+
+            let precision = pool_info
+                .asset_decimals
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default();
+            let compute_d = |amounts: &[Uint128]| -> StdResult<Decimal256> {
+                calculate_stableswap_d(
+                    Uint256::from(2u128),
+                    decimal_with_precision(amounts[0].into(), pool_info.asset_decimals[0])?,
+                    decimal_with_precision(amounts[1].into(), pool_info.asset_decimals[1])?,
+                    amp,
+                    precision,
+                )
+            };
+
+            let new_pool = pool_info
+                .assets
+                .iter()
+                .zip(&deposits)
+                .map(|(pool_asset, deposit)| pool_asset.amount.checked_add(*deposit))
+                .collect::<Result<Vec<_>, _>>()?;
+            let d_1 = compute_d(&new_pool)?;
+
+            if total_share.is_zero() {
+                return initial_share(to_uint256_with_precision(d_1, precision)?);
+            }
+
+            let old_pool = pool_info
+                .assets
+                .iter()
+                .map(|pool_asset| pool_asset.amount)
+                .collect::<Vec<_>>();
+            let d_0 = compute_d(&old_pool)?;
+
+            if d_1 <= d_0 {
+                Uint128::zero()
+            } else {
+                // total_share * (d_1 - d_0) / d_0, on the atomics to only round once
+                Uint128::try_from(
+                    Uint256::from(total_share)
+                        .checked_multiply_ratio(
+                            d_1.atomics().checked_sub(d_0.atomics())?,
+                            d_0.atomics(),
+                        )
+                        .map_err(|e| StdError::generic_err(e.to_string()))?,
+                )?
+            }
+        }
+        PoolType::Weighted { .. } | PoolType::CappedRange { .. } => {
+            return Err(StdError::generic_err(format!(
+                "Liquidity math is not supported for {} pools",
+                pool_info.pool_type.get_label()
+            )));
+        }
+    };
+
+    ensure!(
+        !share.is_zero(),
+        StdError::generic_err("The deposit is too small to mint any LP shares")
+    );
+
+    Ok(share)
+}
+
+/// Computes the assets returned when burning `lp_amount` out of the `total_share` LP supply of
+/// the pool. Each amount is rounded down.
+pub fn compute_withdrawal(
+    pool_info: &PoolInfo,
+    total_share: Uint128,
+    lp_amount: Uint128,
+) -> StdResult<Vec<Coin>> {
+    ensure!(
+        !lp_amount.is_zero() && lp_amount <= total_share,
+        StdError::generic_err(format!(
+            "Invalid amount of LP shares to withdraw: {lp_amount}"
+        ))
+    );
+
+    pool_info
+        .assets
+        .iter()
+        .map(|pool_asset| {
+            Ok(Coin {
+                denom: pool_asset.denom.clone(),
+                amount: pool_asset
+                    .amount
+                    .checked_multiply_ratio(lp_amount, total_share)
+                    .map_err(|e| StdError::generic_err(e.to_string()))?,
+            })
+        })
+        .collect()
+}
+
+/// The outcome of providing liquidity to a pool with a single asset.
+#[cw_serde]
+pub struct SingleSidedDeposit {
+    /// The half of the deposit swapped into the other asset of the pool.
+    pub swap_asset: Coin,
+    /// The simulation of the swap.
+    pub swap_simulation: SimulationResponse,
+    /// The assets deposited after the swap, in the same order as the pool assets.
+    pub deposits: Vec<Coin>,
+    /// The LP shares minted for the deposits.
+    pub lp_amount: Uint128,
+}
+
+/// Computes the outcome of providing liquidity with the single asset `deposit` to a pool with 2
+/// assets. Half of the deposit is swapped into the other asset, and the rest is deposited along
+/// with the swap return into the pool, as the pool manager does.
+///
+/// When `swap_max_slippage` is set, the swap fails if its slippage exceeds it.
+pub fn compute_single_sided_deposit(
+    pool_info: &PoolInfo,
+    total_share: Uint128,
+    deposit: &Coin,
+    swap_max_slippage: Option<Decimal>,
+) -> StdResult<SingleSidedDeposit> {
+    ensure!(
+        pool_info.assets.len() == 2,
+        StdError::generic_err("Single asset deposits are only supported for pools with 2 assets")
+    );
+    ensure!(
+        !total_share.is_zero(),
+        StdError::generic_err("Cannot provide single asset liquidity to an empty pool")
+    );
+
+    let offer_index = pool_info
+        .asset_denoms
+        .iter()
+        .position(|denom| denom == &deposit.denom)
+        .ok_or_else(|| {
+            StdError::generic_err(format!("Asset {} not found in pool", deposit.denom))
+        })?;
+    let ask_index = 1 - offer_index;
+
+    let swap_asset = Coin {
+        denom: deposit.denom.clone(),
+        amount: deposit.amount.checked_div(Uint128::new(2))?,
+    };
+    let swap_simulation =
+        simulate_swap(pool_info, &swap_asset, &pool_info.asset_denoms[ask_index])?;
+
+    if let Some(max_slippage) = swap_max_slippage {
+        let slippage = Decimal::checked_from_ratio(
+            swap_simulation.slippage_amount,
+            swap_simulation
+                .return_amount
+                .checked_add(swap_simulation.slippage_amount)?,
+        )
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+        ensure!(
+            slippage <= max_slippage,
+            StdError::generic_err(format!(
+                "The swap slippage of {slippage} exceeds the maximum of {max_slippage}"
+            ))
+        );
+    }
+
+    // the swap fee stays in the pool, while the rest of the fees leave it
+    let mut swapped_pool = pool_info.clone();
+    swapped_pool.assets[offer_index].amount = swapped_pool.assets[offer_index]
+        .amount
+        .checked_add(swap_asset.amount)?;
+    swapped_pool.assets[ask_index].amount = swapped_pool.assets[ask_index]
+        .amount
+        .checked_sub(swap_simulation.return_amount)?
+        .checked_sub(swap_simulation.protocol_fee_amount)?
+        .checked_sub(swap_simulation.burn_fee_amount)?
+        .checked_sub(swap_simulation.extra_fees_amount)?;
+
+    let mut deposits = vec![Coin::default(), Coin::default()];
+    deposits[offer_index] = Coin {
+        denom: deposit.denom.clone(),
+        amount: deposit.amount.checked_sub(swap_asset.amount)?,
+    };
+    deposits[ask_index] = Coin {
+        denom: pool_info.asset_denoms[ask_index].clone(),
+        amount: swap_simulation.return_amount,
+    };
+
+    let lp_amount = compute_lp_mint_amount(&swapped_pool, total_share, &deposits)?;
+
+    Ok(SingleSidedDeposit {
+        swap_asset,
+        swap_simulation,
+        deposits,
+        lp_amount,
+    })
+}
+
+/// Gets the amount deposited for each of the pool assets, in the same order. Every pool asset
+/// must be deposited.
+fn get_deposit_amounts(pool_info: &PoolInfo, deposits: &[Coin]) -> StdResult<Vec<Uint128>> {
+    ensure!(
+        deposits.len() == pool_info.asset_denoms.len(),
+        StdError::generic_err("All the pool assets must be deposited")
+    );
+
+    pool_info
+        .asset_denoms
+        .iter()
+        .map(|denom| {
+            deposits
+                .iter()
+                .find(|deposit| &deposit.denom == denom && !deposit.amount.is_zero())
+                .map(|deposit| deposit.amount)
+                .ok_or_else(|| StdError::generic_err(format!("Missing deposit of {denom}")))
+        })
+        .collect()
+}
+
+/// Gets the shares minted on the first deposit of a pool, after locking
+/// [MINIMUM_LIQUIDITY_AMOUNT].
+fn initial_share(share: Uint256) -> StdResult<Uint128> {
+    let share = Uint128::try_from(share)?.saturating_sub(MINIMUM_LIQUIDITY_AMOUNT);
+    ensure!(
+        !share.is_zero(),
+        StdError::generic_err(format!(
+            "The initial liquidity must mint more than {MINIMUM_LIQUIDITY_AMOUNT} LP shares"
+        ))
+    );
+    Ok(share)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Decimal, StdError, Uint128};
+
+    use crate::lp_common::{
+        compute_lp_mint_amount, compute_single_sided_deposit, compute_withdrawal,
+    };
+    use crate::pool_manager::PoolType;
+    use crate::testing::om_usdc_pool;
+
+    #[test]
+    fn constant_product_lp_shares() {
+        let deposits = [coin(1_000_000, "uom"), coin(4_000_000, "uusdc")];
+
+        // sqrt(1_000_000 * 4_000_000) - 1_000
+        assert_eq!(
+            compute_lp_mint_amount(
+                &om_usdc_pool(0, 0, PoolType::ConstantProduct),
+                Uint128::zero(),
+                &deposits
+            )
+            .unwrap(),
+            Uint128::new(1_999_000)
+        );
+        // the smallest share of the deposits is minted
+        assert_eq!(
+            compute_lp_mint_amount(
+                &om_usdc_pool(2_000_000, 4_000_000, PoolType::ConstantProduct),
+                Uint128::new(2_000_000),
+                &deposits
+            )
+            .unwrap(),
+            Uint128::new(1_000_000)
+        );
+        assert_eq!(
+            compute_lp_mint_amount(
+                &om_usdc_pool(0, 0, PoolType::ConstantProduct),
+                Uint128::zero(),
+                &[coin(1_000, "uom"), coin(1_000, "uusdc")]
+            ),
+            Err(StdError::generic_err(
+                "The initial liquidity must mint more than 1000 LP shares"
+            ))
+        );
+        assert!(compute_lp_mint_amount(
+            &om_usdc_pool(0, 0, PoolType::ConstantProduct),
+            Uint128::zero(),
+            &[coin(1_000_000, "uom")]
+        )
+        .is_err());
+
+        let mut three_asset_pool = om_usdc_pool(0, 0, PoolType::ConstantProduct);
+        three_asset_pool.asset_denoms.push("uweth".to_string());
+        three_asset_pool.asset_decimals.push(18);
+        three_asset_pool.assets.push(coin(0, "uweth"));
+        assert_eq!(
+            compute_lp_mint_amount(
+                &three_asset_pool,
+                Uint128::zero(),
+                &[
+                    coin(1_000_000, "uom"),
+                    coin(4_000_000, "uusdc"),
+                    coin(1_000_000, "uweth")
+                ]
+            ),
+            Err(StdError::generic_err(
+                "Liquidity math is only supported for constant product pools with 2 assets"
+            ))
+        );
+    }
+
+    #[test]
+    fn stableswap_lp_shares() {
+        let pool_type = PoolType::StableSwap { amp: 100 };
+        let deposits = [coin(1_000_000, "uom"), coin(1_000_000, "uusdc")];
+
+        let initial_shares = compute_lp_mint_amount(
+            &om_usdc_pool(0, 0, pool_type.clone()),
+            Uint128::zero(),
+            &deposits,
+        )
+        .unwrap();
+        assert_eq!(initial_shares, Uint128::new(1_999_000));
+
+        // a balanced deposit doubling the pool doubles the supply
+        assert_eq!(
+            compute_lp_mint_amount(
+                &om_usdc_pool(1_000_000, 1_000_000, pool_type),
+                Uint128::new(2_000_000),
+                &deposits
+            )
+            .unwrap(),
+            Uint128::new(2_000_000)
+        );
+    }
+
+    #[test]
+    fn withdraw_lp_shares() {
+        let pool = om_usdc_pool(1_000_000, 3_000_001, PoolType::ConstantProduct);
+
+        assert_eq!(
+            compute_withdrawal(&pool, Uint128::new(2_000_000), Uint128::new(500_000)).unwrap(),
+            vec![coin(250_000, "uom"), coin(750_000, "uusdc")]
+        );
+        assert!(
+            compute_withdrawal(&pool, Uint128::new(2_000_000), Uint128::new(2_000_001)).is_err()
+        );
+    }
+
+    #[test]
+    fn single_sided_deposit() {
+        let pool = om_usdc_pool(1_000_000, 1_000_000, PoolType::ConstantProduct);
+
+        let deposit = compute_single_sided_deposit(
+            &pool,
+            Uint128::new(1_000_000),
+            &coin(20_000, "uom"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(deposit.swap_asset, coin(10_000, "uom"));
+        assert_eq!(deposit.deposits[0], coin(10_000, "uom"));
+        assert_eq!(
+            deposit.deposits[1],
+            coin(deposit.swap_simulation.return_amount.u128(), "uusdc")
+        );
+        assert!(!deposit.lp_amount.is_zero());
+
+        assert!(compute_single_sided_deposit(
+            &pool,
+            Uint128::new(1_000_000),
+            &coin(20_000, "uom"),
+            Some(Decimal::permille(1))
+        )
+        .is_err());
+        assert!(compute_single_sided_deposit(
+            &pool,
+            Uint128::new(1_000_000),
+            &coin(20_000, "uatom"),
+            None
+        )
+        .is_err());
+    }
+}
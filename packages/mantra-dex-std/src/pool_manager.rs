@@ -1,11 +1,19 @@
 use std::fmt;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, Decimal, Deps, StdError, StdResult, Uint128};
+use cosmwasm_std::{
+    ensure, Addr, Binary, Coin, Decimal, Decimal256, Deps, StdError, StdResult, Uint128,
+};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
-use crate::coin::is_factory_token;
+use crate::coin::{
+    aggregate_coins, get_factory_token_creator, get_factory_token_subdenom, is_factory_token,
+    FACTORY_MAX_SUBDENOM_SIZE, FACTORY_PREFIX,
+};
+use crate::constants::LP_SYMBOL;
 use crate::fee::PoolFee;
+use crate::limit_order::LimitOrder;
+use crate::tokenfactory::utils::get_factory_denom_creation_fee;
 
 /// The type of swap operation to perform.
 #[cw_serde]
@@ -82,7 +90,8 @@ pub struct PoolInfo {
     pub status: PoolStatus,
 }
 
-/// Possible pool types, it can be either a constant product (xyk) pool or a stable swap pool.
+/// Possible pool types, it can be either a constant product (xyk) pool, a stable swap pool, a
+/// weighted pool or a capped range pool.
 #[cw_serde]
 pub enum PoolType {
     /// A stable swap pool.
@@ -92,6 +101,21 @@ pub enum PoolType {
     },
     /// xyk pool
     ConstantProduct,
+    /// A weighted pool, i.e. a constant product pool where each asset has a different weight in
+    /// the invariant, e.g. an 80/20 pool.
+    Weighted {
+        /// The weights for the assets, provided in the same order as `asset_denoms`. The weights
+        /// must add up to one.
+        weights: Vec<Decimal>,
+    },
+    /// A two asset pool which only provides liquidity within a price range, e.g. for pegged assets.
+    /// The price is expressed as the amount of the second asset per unit of the first one.
+    CappedRange {
+        /// The lower bound of the price range.
+        lower_price: Decimal,
+        /// The upper bound of the price range.
+        upper_price: Decimal,
+    },
 }
 
 impl PoolType {
@@ -100,6 +124,66 @@ impl PoolType {
         match self {
             PoolType::ConstantProduct => "ConstantProduct",
             PoolType::StableSwap { .. } => "StableSwap",
+            PoolType::Weighted { .. } => "Weighted",
+            PoolType::CappedRange { .. } => "CappedRange",
+        }
+    }
+
+    /// Validates the pool type parameters for a pool with the given amount of assets.
+    pub fn validate(&self, assets_count: usize) -> StdResult<()> {
+        match self {
+            PoolType::ConstantProduct => Ok(()),
+            PoolType::StableSwap { amp } => {
+                ensure!(
+                    *amp > 0u64,
+                    StdError::generic_err("The amplification factor cannot be zero")
+                );
+                Ok(())
+            }
+            PoolType::Weighted { weights } => {
+                ensure!(
+                    weights.len() == assets_count,
+                    StdError::generic_err(format!(
+                        "Invalid amount of weights, expected {assets_count}, got {}",
+                        weights.len()
+                    ))
+                );
+                ensure!(
+                    weights.iter().all(|weight| !weight.is_zero()),
+                    StdError::generic_err("Pool weights cannot be zero")
+                );
+
+                let total_weight = weights
+                    .iter()
+                    .try_fold(Decimal::zero(), |acc, weight| acc.checked_add(*weight))?;
+                ensure!(
+                    total_weight == Decimal::one(),
+                    StdError::generic_err(format!(
+                        "Pool weights must add up to one, got {total_weight}"
+                    ))
+                );
+                Ok(())
+            }
+            PoolType::CappedRange {
+                lower_price,
+                upper_price,
+            } => {
+                ensure!(
+                    assets_count == 2usize,
+                    StdError::generic_err("Capped range pools must have exactly two assets")
+                );
+                ensure!(
+                    !lower_price.is_zero(),
+                    StdError::generic_err("The lower price of the range cannot be zero")
+                );
+                ensure!(
+                    lower_price < upper_price,
+                    StdError::generic_err(
+                        "The lower price of the range must be lower than the upper price"
+                    )
+                );
+                Ok(())
+            }
         }
     }
 }
@@ -234,6 +318,46 @@ pub enum ExecuteMsg {
         /// control over which operations are allowed, i.e. swap, deposits, withdrawals
         feature_toggle: Option<FeatureToggle>,
     },
+    /// Places a limit order, swapping the offer asset sent along with the message into the ask
+    /// asset once the pool price allows receiving at least `trigger_price` ask assets per offer
+    /// asset, after fees.
+    PlaceLimitOrder {
+        /// The identifier of the pool to swap in.
+        pool_identifier: String,
+        /// The return asset of the swap.
+        ask_asset_denom: String,
+        /// The minimum price, in ask assets per offer asset, the order is executed at.
+        trigger_price: Decimal,
+        /// The recipient of the output tokens. If not provided, the tokens will be sent to the
+        /// sender of the message.
+        receiver: Option<String>,
+        /// The time (unix timestamp), in seconds, after which the order can no longer be executed.
+        /// If not provided, the order doesn't expire.
+        expires_at: Option<u64>,
+    },
+    /// Cancels a limit order, returning the offer asset to its owner. Expired orders can be
+    /// cancelled by anyone.
+    CancelLimitOrder {
+        /// The id of the order to cancel.
+        order_id: u64,
+    },
+    /// Executes the given limit orders, if triggered. Meant to be called by keepers.
+    ExecuteLimitOrders {
+        /// The ids of the orders to execute.
+        order_ids: Vec<u64>,
+    },
+    /// Lends `amount` out of the pool to the sender, which is called back with a
+    /// [FlashSwapCallbackMsg](crate::flash_swap::FlashSwapCallbackMsg) and must repay the loan
+    /// plus fees within the same transaction, see
+    /// [compute_flash_swap_repayment](crate::flash_swap::compute_flash_swap_repayment).
+    FlashSwap {
+        /// The identifier of the pool to borrow from.
+        pool_identifier: String,
+        /// The asset to borrow.
+        amount: Coin,
+        /// The message passed to the sender in the callback.
+        callback_msg: Binary,
+    },
 }
 
 #[cw_ownable_query]
@@ -301,6 +425,41 @@ pub enum QueryMsg {
         /// the contract.
         limit: Option<u32>,
     },
+    /// Retrieves the time-weighted average price (TWAP) of an asset in terms of another asset of
+    /// the same pool, between the given times.
+    #[returns(TwapResponse)]
+    Twap {
+        /// The pool identifier to do the query for.
+        pool_identifier: String,
+        /// The denom of the asset to get the price for.
+        base_denom: String,
+        /// The denom of the asset the price is expressed in.
+        quote_denom: String,
+        /// The start time of the period to average the price over (unix timestamp), in seconds.
+        start_time: u64,
+        /// The end time of the period to average the price over (unix timestamp), in seconds. If
+        /// unspecified, it will default to the current block time.
+        end_time: Option<u64>,
+    },
+    /// Retrieves a limit order.
+    #[returns(LimitOrderResponse)]
+    LimitOrder {
+        /// The id of the order.
+        order_id: u64,
+    },
+    /// Retrieves the limit orders, optionally filtered by pool or owner.
+    #[returns(LimitOrdersResponse)]
+    LimitOrders {
+        /// An optional parameter to only return the orders of the given pool.
+        pool_identifier: Option<String>,
+        /// An optional parameter to only return the orders of the given owner.
+        owner: Option<String>,
+        /// An optional parameter specifying what order id to start searching after.
+        start_after: Option<u64>,
+        /// The amount of orders to return. If unspecified, will default to a value specified by
+        /// the contract.
+        limit: Option<u32>,
+    },
 }
 
 /// The response for the `Config` query.
@@ -310,6 +469,20 @@ pub struct ConfigResponse {
     pub config: Config,
 }
 
+/// The response for the `LimitOrder` query.
+#[cw_serde]
+pub struct LimitOrderResponse {
+    /// The limit order.
+    pub order: LimitOrder,
+}
+
+/// The response for the `LimitOrders` query.
+#[cw_serde]
+pub struct LimitOrdersResponse {
+    /// The limit orders.
+    pub orders: Vec<LimitOrder>,
+}
+
 /// The response for the `Pools` query.
 #[cw_serde]
 pub struct PoolsResponse {
@@ -370,6 +543,24 @@ pub struct ReverseSimulationResponse {
     pub extra_fees_amount: Uint128,
 }
 
+/// The response for the `Twap` query.
+#[cw_serde]
+pub struct TwapResponse {
+    /// The pool identifier the price was queried for.
+    pub pool_identifier: String,
+    /// The denom of the asset the price is for.
+    pub base_denom: String,
+    /// The denom of the asset the price is expressed in.
+    pub quote_denom: String,
+    /// The start time of the period the price was averaged over, in seconds.
+    pub start_time: u64,
+    /// The end time of the period the price was averaged over, in seconds.
+    pub end_time: u64,
+    /// The time-weighted average price of the base asset in terms of the quote asset, normalized
+    /// by the decimals of both assets.
+    pub twap: Decimal256,
+}
+
 /// Pool feature toggle, can control whether swaps, deposits, and withdrawals are enabled.
 #[cw_serde]
 pub struct FeatureToggle {
@@ -426,3 +617,439 @@ pub fn get_total_share(deps: &Deps, liquidity_asset: String) -> StdResult<Uint12
 
     Err(StdError::generic_err("Invalid LP token"))
 }
+
+/// Quotes the exact funds to send along with a `CreatePool` message to the given pool manager,
+/// i.e. its `Config::pool_creation_fee` plus the token factory denom creation fee for the LP
+/// denom.
+pub fn quote_pool_creation_funds(
+    deps: Deps,
+    pool_manager_addr: impl Into<String>,
+) -> StdResult<Vec<Coin>> {
+    let config_response: ConfigResponse = deps
+        .querier
+        .query_wasm_smart(pool_manager_addr, &QueryMsg::Config {})?;
+    let denom_creation_fee = get_factory_denom_creation_fee(deps)?;
+
+    get_pool_creation_funds(config_response.config.pool_creation_fee, denom_creation_fee)
+}
+
+/// Merges the pool creation fee and the token factory denom creation fee into the funds to send
+/// along with a `CreatePool` message, dropping zero amounts.
+pub fn get_pool_creation_funds(
+    pool_creation_fee: Coin,
+    denom_creation_fee: Vec<Coin>,
+) -> StdResult<Vec<Coin>> {
+    let mut funds = denom_creation_fee;
+    funds.push(pool_creation_fee);
+    funds.retain(|coin| !coin.amount.is_zero());
+
+    aggregate_coins(funds)
+}
+
+/// The prefix of the pool identifiers supplied by the pool creator.
+pub const USER_POOL_IDENTIFIER_PREFIX: &str = "o";
+/// The prefix of the pool identifiers generated by the pool manager.
+pub const GENERATED_POOL_IDENTIFIER_PREFIX: &str = "p";
+/// The maximum length of a pool identifier, so that its LP subdenom, i.e.
+/// `{pool_identifier}.{LP_SYMBOL}`, fits in [FACTORY_MAX_SUBDENOM_SIZE].
+pub const MAX_POOL_IDENTIFIER_LENGTH: usize = FACTORY_MAX_SUBDENOM_SIZE - LP_SYMBOL.len() - 1;
+
+/// Validates a pool identifier. It must be non-empty, at most [MAX_POOL_IDENTIFIER_LENGTH]
+/// characters long, and made of dot separated, non-empty segments of alphanumeric characters.
+pub fn validate_pool_identifier(pool_identifier: &str) -> StdResult<()> {
+    ensure!(
+        !pool_identifier.is_empty() && pool_identifier.len() <= MAX_POOL_IDENTIFIER_LENGTH,
+        StdError::generic_err(format!(
+            "Invalid pool identifier length {}, must be between 1 and {MAX_POOL_IDENTIFIER_LENGTH}",
+            pool_identifier.len()
+        ))
+    );
+    ensure!(
+        pool_identifier
+            .split('.')
+            .all(|segment| !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric())),
+        StdError::generic_err(format!(
+            "Invalid pool identifier {pool_identifier}, only dot separated alphanumeric segments are allowed"
+        ))
+    );
+
+    Ok(())
+}
+
+/// Gets the identifier the pool manager assigns to a new pool. A pool identifier supplied by the
+/// creator is prefixed with [USER_POOL_IDENTIFIER_PREFIX], otherwise the identifier is generated
+/// from the pool counter and prefixed with [GENERATED_POOL_IDENTIFIER_PREFIX], so that both never
+/// collide.
+pub fn get_pool_identifier(pool_identifier: Option<&str>, pool_counter: u64) -> StdResult<String> {
+    let pool_identifier = match pool_identifier {
+        Some(pool_identifier) => format!("{USER_POOL_IDENTIFIER_PREFIX}.{pool_identifier}"),
+        None => format!("{GENERATED_POOL_IDENTIFIER_PREFIX}.{pool_counter}"),
+    };
+    validate_pool_identifier(&pool_identifier)?;
+
+    Ok(pool_identifier)
+}
+
+/// Derives the LP denom of a pool, i.e. `factory/{pool_manager}/{pool_identifier}.{LP_SYMBOL}`.
+pub fn get_lp_denom(pool_manager_addr: &str, pool_identifier: &str) -> StdResult<String> {
+    validate_pool_identifier(pool_identifier)?;
+
+    let lp_denom = format!("{FACTORY_PREFIX}/{pool_manager_addr}/{pool_identifier}.{LP_SYMBOL}");
+    ensure!(
+        is_factory_token(&lp_denom),
+        StdError::generic_err(format!("Invalid LP denom {lp_denom}"))
+    );
+
+    Ok(lp_denom)
+}
+
+/// The parts of an LP denom, see [get_lp_denom].
+#[cw_serde]
+pub struct LpDenom {
+    /// The address of the pool manager that created the LP denom.
+    pub pool_manager_addr: String,
+    /// The identifier of the pool.
+    pub pool_identifier: String,
+}
+
+/// Parses an LP denom into the pool manager address and pool identifier it was derived from.
+pub fn parse_lp_denom(lp_denom: &str) -> StdResult<LpDenom> {
+    let invalid_lp_denom = || StdError::generic_err(format!("Invalid LP denom {lp_denom}"));
+    ensure!(is_factory_token(lp_denom), invalid_lp_denom());
+
+    let pool_identifier = get_factory_token_subdenom(lp_denom)?
+        .strip_suffix(LP_SYMBOL)
+        .and_then(|subdenom| subdenom.strip_suffix('.'))
+        .ok_or_else(invalid_lp_denom)?;
+    validate_pool_identifier(pool_identifier).map_err(|_| invalid_lp_denom())?;
+
+    Ok(LpDenom {
+        pool_manager_addr: get_factory_token_creator(lp_denom)?.to_string(),
+        pool_identifier: pool_identifier.to_string(),
+    })
+}
+
+/// Validates the parameters of a `CreatePool` message, i.e. that there are at least two distinct
+/// assets, that there are decimals for each of them, and that both the fees and the pool type
+/// are valid. The total fees cannot exceed the pool manager's `max_total_fee`, see
+/// [PoolFee::is_valid_with_max_total_fee].
+pub fn validate_create_pool_params(
+    asset_denoms: &[String],
+    asset_decimals: &[u8],
+    pool_fees: &PoolFee,
+    pool_type: &PoolType,
+    max_total_fee: Decimal,
+) -> StdResult<()> {
+    ensure!(
+        asset_denoms.len() >= 2usize,
+        StdError::generic_err("A pool must have at least two assets")
+    );
+    ensure!(
+        asset_denoms.len() == asset_decimals.len(),
+        StdError::generic_err("The amount of asset denoms and asset decimals must match")
+    );
+    ensure!(
+        asset_denoms
+            .iter()
+            .enumerate()
+            .all(|(i, denom)| !asset_denoms[..i].contains(denom)),
+        StdError::generic_err("A pool cannot have repeated assets")
+    );
+
+    pool_fees.is_valid_with_max_total_fee(max_total_fee)?;
+    pool_type.validate(asset_denoms.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{MockApi, MockStorage};
+    use cosmwasm_std::{
+        coin, from_json, to_json_binary, Addr, Binary, ContractResult, Decimal, Deps, Empty,
+        GrpcQuery, Querier, QuerierResult, QuerierWrapper, QueryRequest, StdError, SystemError,
+        SystemResult, WasmQuery,
+    };
+    use mantrachain_std::types::cosmos::base::v1beta1::Coin as ProtoCoin;
+    use mantrachain_std::types::osmosis::tokenfactory::v1beta1::QueryParamsResponse;
+    use mantrachain_std::types::osmosis::tokenfactory::Params;
+
+    use crate::fee::{Fee, PoolFee, DEFAULT_MAX_TOTAL_FEE};
+    use crate::pool_manager::{
+        get_lp_denom, get_pool_creation_funds, get_pool_identifier, parse_lp_denom,
+        quote_pool_creation_funds, validate_create_pool_params, validate_pool_identifier, Config,
+        ConfigResponse, LpDenom, PoolType, QueryMsg,
+    };
+
+    /// Answers the pool manager `Config` query and the token factory `Params` query.
+    struct PoolCreationQuerier {
+        denom_creation_fee: Vec<ProtoCoin>,
+    }
+
+    impl Querier for PoolCreationQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let response = match from_json::<QueryRequest<Empty>>(bin_request).unwrap() {
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                    if contract_addr == "pool_manager"
+                        && matches!(from_json(&msg).unwrap(), QueryMsg::Config {}) =>
+                {
+                    to_json_binary(&ConfigResponse {
+                        config: Config {
+                            fee_collector_addr: Addr::unchecked("fee_collector"),
+                            farm_manager_addr: Addr::unchecked("farm_manager"),
+                            pool_creation_fee: coin(1_000, "uom"),
+                        },
+                    })
+                    .unwrap()
+                }
+                QueryRequest::Grpc(GrpcQuery { path, .. })
+                    if path == "/osmosis.tokenfactory.v1beta1.Query/Params" =>
+                {
+                    Binary::from(QueryParamsResponse {
+                        params: Some(Params {
+                            denom_creation_fee: self.denom_creation_fee.clone(),
+                            denom_creation_gas_consume: None,
+                            fee_collector_address: String::new(),
+                        }),
+                    })
+                }
+                request => {
+                    return SystemResult::Err(SystemError::UnsupportedRequest {
+                        kind: format!("{request:?}"),
+                    })
+                }
+            };
+
+            SystemResult::Ok(ContractResult::Ok(response))
+        }
+    }
+
+    fn quote(denom_creation_fee: &[(&str, &str)]) -> Result<Vec<cosmwasm_std::Coin>, StdError> {
+        let querier = PoolCreationQuerier {
+            denom_creation_fee: denom_creation_fee
+                .iter()
+                .map(|(amount, denom)| ProtoCoin {
+                    denom: denom.to_string(),
+                    amount: amount.to_string(),
+                })
+                .collect(),
+        };
+        let deps = Deps {
+            storage: &MockStorage::new(),
+            api: &MockApi::default(),
+            querier: QuerierWrapper::new(&querier),
+        };
+
+        quote_pool_creation_funds(deps, "pool_manager")
+    }
+
+    fn pool_fees() -> PoolFee {
+        PoolFee {
+            protocol_fee: Fee {
+                share: Decimal::permille(1),
+            },
+            swap_fee: Fee {
+                share: Decimal::permille(2),
+            },
+            burn_fee: Fee {
+                share: Decimal::zero(),
+            },
+            extra_fees: vec![],
+        }
+    }
+
+    #[test]
+    fn validate_weighted_pool_type() {
+        let pool_type = PoolType::Weighted {
+            weights: vec![Decimal::percent(80), Decimal::percent(20)],
+        };
+        assert!(pool_type.validate(2).is_ok());
+        assert_eq!(pool_type.get_label(), "Weighted");
+        assert_eq!(
+            pool_type.validate(3),
+            Err(StdError::generic_err(
+                "Invalid amount of weights, expected 3, got 2"
+            ))
+        );
+
+        let pool_type = PoolType::Weighted {
+            weights: vec![Decimal::percent(80), Decimal::percent(30)],
+        };
+        assert_eq!(
+            pool_type.validate(2),
+            Err(StdError::generic_err(
+                "Pool weights must add up to one, got 1.1"
+            ))
+        );
+
+        let pool_type = PoolType::Weighted {
+            weights: vec![Decimal::one(), Decimal::zero()],
+        };
+        assert_eq!(
+            pool_type.validate(2),
+            Err(StdError::generic_err("Pool weights cannot be zero"))
+        );
+    }
+
+    #[test]
+    fn validate_capped_range_pool_type() {
+        let pool_type = PoolType::CappedRange {
+            lower_price: Decimal::percent(98),
+            upper_price: Decimal::percent(102),
+        };
+        assert!(pool_type.validate(2).is_ok());
+        assert_eq!(pool_type.get_label(), "CappedRange");
+        assert_eq!(
+            pool_type.validate(3),
+            Err(StdError::generic_err(
+                "Capped range pools must have exactly two assets"
+            ))
+        );
+
+        let pool_type = PoolType::CappedRange {
+            lower_price: Decimal::percent(102),
+            upper_price: Decimal::percent(98),
+        };
+        assert_eq!(
+            pool_type.validate(2),
+            Err(StdError::generic_err(
+                "The lower price of the range must be lower than the upper price"
+            ))
+        );
+    }
+
+    #[test]
+    fn validate_create_pool() {
+        let denoms = vec!["uom".to_string(), "uusdc".to_string()];
+
+        assert!(validate_create_pool_params(
+            &denoms,
+            &[6, 6],
+            &pool_fees(),
+            &PoolType::ConstantProduct,
+            DEFAULT_MAX_TOTAL_FEE
+        )
+        .is_ok());
+        assert_eq!(
+            validate_create_pool_params(
+                &denoms,
+                &[6],
+                &pool_fees(),
+                &PoolType::ConstantProduct,
+                DEFAULT_MAX_TOTAL_FEE
+            ),
+            Err(StdError::generic_err(
+                "The amount of asset denoms and asset decimals must match"
+            ))
+        );
+        assert_eq!(
+            validate_create_pool_params(
+                &["uom".to_string(), "uom".to_string()],
+                &[6, 6],
+                &pool_fees(),
+                &PoolType::ConstantProduct,
+                DEFAULT_MAX_TOTAL_FEE
+            ),
+            Err(StdError::generic_err("A pool cannot have repeated assets"))
+        );
+        assert_eq!(
+            validate_create_pool_params(
+                &denoms,
+                &[6, 6],
+                &pool_fees(),
+                &PoolType::StableSwap { amp: 0 },
+                DEFAULT_MAX_TOTAL_FEE
+            ),
+            Err(StdError::generic_err(
+                "The amplification factor cannot be zero"
+            ))
+        );
+        // the fees are validated against the given cap
+        assert!(validate_create_pool_params(
+            &denoms,
+            &[6, 6],
+            &pool_fees(),
+            &PoolType::ConstantProduct,
+            Decimal::permille(2)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn pool_creation_funds() {
+        assert_eq!(
+            get_pool_creation_funds(
+                coin(1_000, "uom"),
+                vec![coin(500, "uom"), coin(0, "uusdc"), coin(10, "uatom")]
+            )
+            .unwrap(),
+            vec![coin(10, "uatom"), coin(1_500, "uom")]
+        );
+        assert_eq!(
+            get_pool_creation_funds(coin(0, "uom"), vec![]).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn quote_pool_creation_funds_from_queries() {
+        assert_eq!(
+            quote(&[("500", "uom"), ("10", "uatom")]).unwrap(),
+            vec![coin(10, "uatom"), coin(1_500, "uom")]
+        );
+        assert_eq!(quote(&[]).unwrap(), vec![coin(1_000, "uom")]);
+
+        let err = quote(&[("-5", "uom")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid denom creation fee amount -5"));
+    }
+
+    #[test]
+    fn pool_identifiers() {
+        assert_eq!(get_pool_identifier(None, 7).unwrap(), "p.7");
+        assert_eq!(
+            get_pool_identifier(Some("uom.uusdc"), 7).unwrap(),
+            "o.uom.uusdc"
+        );
+        assert!(get_pool_identifier(Some("uom-uusdc"), 7).is_err());
+        assert!(get_pool_identifier(Some(&"a".repeat(40)), 7).is_err());
+
+        assert!(validate_pool_identifier(&"a".repeat(41)).is_ok());
+        assert!(validate_pool_identifier("").is_err());
+        assert!(validate_pool_identifier("o..uom").is_err());
+        assert!(validate_pool_identifier("ibc/uom").is_err());
+    }
+
+    #[test]
+    fn lp_denoms() {
+        let pool_manager = "mantra1zwv6feuzhy6a9wekh96cd57lsarmqlwxdypdsplw6zhfncqw6ftqlydlr9";
+
+        let lp_denom = get_lp_denom(pool_manager, "o.uom.uusdc.pool.0").unwrap();
+        assert_eq!(
+            lp_denom,
+            format!("factory/{pool_manager}/o.uom.uusdc.pool.0.LP")
+        );
+        assert_eq!(
+            parse_lp_denom(&lp_denom).unwrap(),
+            LpDenom {
+                pool_manager_addr: pool_manager.to_string(),
+                pool_identifier: "o.uom.uusdc.pool.0".to_string(),
+            }
+        );
+
+        // the longest identifier still makes a valid factory token
+        let lp_denom = get_lp_denom(pool_manager, &"a".repeat(41)).unwrap();
+        assert_eq!(
+            parse_lp_denom(&lp_denom).unwrap().pool_identifier,
+            "a".repeat(41)
+        );
+
+        assert_eq!(
+            parse_lp_denom("factory/pool_manager/o.uom.uusdc"),
+            Err(StdError::generic_err(
+                "Invalid LP denom factory/pool_manager/o.uom.uusdc"
+            ))
+        );
+        assert!(parse_lp_denom("factory/pool_manager/.LP").is_err());
+        assert!(parse_lp_denom("uom").is_err());
+    }
+}
@@ -0,0 +1,413 @@
+use cosmwasm_std::{
+    ensure, to_json_binary, Coin, CosmosMsg, Decimal, StdError, StdResult, WasmMsg,
+};
+
+use crate::coin::aggregate_coins;
+use crate::fee::PoolFee;
+use crate::pool_manager::{get_pool_creation_funds, ExecuteMsg, PoolType};
+
+/// Builds pool manager [ExecuteMsg]s wrapped in a [WasmMsg::Execute], attaching the funds each
+/// message requires.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolManagerMsgBuilder {
+    contract_addr: String,
+}
+
+impl PoolManagerMsgBuilder {
+    /// Creates a new builder for the pool manager at the given address.
+    pub fn new(contract_addr: impl Into<String>) -> Self {
+        PoolManagerMsgBuilder {
+            contract_addr: contract_addr.into(),
+        }
+    }
+
+    /// Starts building a `CreatePool` message.
+    pub fn create_pool(
+        &self,
+        asset_denoms: Vec<String>,
+        asset_decimals: Vec<u8>,
+        pool_fees: PoolFee,
+        pool_type: PoolType,
+    ) -> CreatePoolBuilder {
+        CreatePoolBuilder {
+            contract_addr: self.contract_addr.clone(),
+            asset_denoms,
+            asset_decimals,
+            pool_fees,
+            pool_type,
+            pool_identifier: None,
+            pool_creation_fee: None,
+            denom_creation_fee: vec![],
+        }
+    }
+
+    /// Starts building a `ProvideLiquidity` message depositing the given assets. If a single
+    /// asset is provided, the liquidity is provided single-sided.
+    pub fn provide_liquidity(
+        &self,
+        pool_identifier: impl Into<String>,
+        assets: Vec<Coin>,
+    ) -> ProvideLiquidityBuilder {
+        ProvideLiquidityBuilder {
+            contract_addr: self.contract_addr.clone(),
+            pool_identifier: pool_identifier.into(),
+            assets,
+            liquidity_max_slippage: None,
+            swap_max_slippage: None,
+            receiver: None,
+            unlocking_duration: None,
+            lock_position_identifier: None,
+        }
+    }
+
+    /// Starts building a `Swap` message, offering the given asset.
+    pub fn swap(
+        &self,
+        pool_identifier: impl Into<String>,
+        offer_asset: Coin,
+        ask_asset_denom: impl Into<String>,
+    ) -> SwapBuilder {
+        SwapBuilder {
+            contract_addr: self.contract_addr.clone(),
+            pool_identifier: pool_identifier.into(),
+            offer_asset,
+            ask_asset_denom: ask_asset_denom.into(),
+            belief_price: None,
+            max_slippage: None,
+            receiver: None,
+        }
+    }
+
+    /// Builds a `WithdrawLiquidity` message, sending the given LP tokens to the pool manager.
+    pub fn withdraw_liquidity(
+        &self,
+        pool_identifier: impl Into<String>,
+        lp_asset: Coin,
+    ) -> StdResult<CosmosMsg> {
+        ensure_non_zero(&lp_asset)?;
+
+        execute_msg(
+            &self.contract_addr,
+            &ExecuteMsg::WithdrawLiquidity {
+                pool_identifier: pool_identifier.into(),
+            },
+            vec![lp_asset],
+        )
+    }
+}
+
+/// Builder for the `CreatePool` message. Created with [PoolManagerMsgBuilder::create_pool].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreatePoolBuilder {
+    contract_addr: String,
+    asset_denoms: Vec<String>,
+    asset_decimals: Vec<u8>,
+    pool_fees: PoolFee,
+    pool_type: PoolType,
+    pool_identifier: Option<String>,
+    pool_creation_fee: Option<Coin>,
+    denom_creation_fee: Vec<Coin>,
+}
+
+impl CreatePoolBuilder {
+    /// Sets the identifier for the pool.
+    pub fn pool_identifier(mut self, pool_identifier: impl Into<String>) -> Self {
+        self.pool_identifier = Some(pool_identifier.into());
+        self
+    }
+
+    /// Sets the pool creation fee, as defined in the pool manager's `Config::pool_creation_fee`.
+    pub fn pool_creation_fee(mut self, pool_creation_fee: Coin) -> Self {
+        self.pool_creation_fee = Some(pool_creation_fee);
+        self
+    }
+
+    /// Sets the token factory denom creation fee, as returned by
+    /// [crate::tokenfactory::utils::get_factory_denom_creation_fee].
+    pub fn denom_creation_fee(mut self, denom_creation_fee: Vec<Coin>) -> Self {
+        self.denom_creation_fee = denom_creation_fee;
+        self
+    }
+
+    /// Builds the message, attaching both the pool creation fee and the denom creation fee.
+    pub fn build(self) -> StdResult<CosmosMsg> {
+        let pool_creation_fee = self
+            .pool_creation_fee
+            .ok_or_else(|| StdError::generic_err("The pool creation fee must be provided"))?;
+
+        execute_msg(
+            &self.contract_addr,
+            &ExecuteMsg::CreatePool {
+                asset_denoms: self.asset_denoms,
+                asset_decimals: self.asset_decimals,
+                pool_fees: self.pool_fees,
+                pool_type: self.pool_type,
+                pool_identifier: self.pool_identifier,
+            },
+            get_pool_creation_funds(pool_creation_fee, self.denom_creation_fee)?,
+        )
+    }
+}
+
+/// Builder for the `ProvideLiquidity` message. Created with
+/// [PoolManagerMsgBuilder::provide_liquidity].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProvideLiquidityBuilder {
+    contract_addr: String,
+    pool_identifier: String,
+    assets: Vec<Coin>,
+    liquidity_max_slippage: Option<Decimal>,
+    swap_max_slippage: Option<Decimal>,
+    receiver: Option<String>,
+    unlocking_duration: Option<u64>,
+    lock_position_identifier: Option<String>,
+}
+
+impl ProvideLiquidityBuilder {
+    /// Sets the maximum slippage for the liquidity provision.
+    pub fn liquidity_max_slippage(mut self, liquidity_max_slippage: Decimal) -> Self {
+        self.liquidity_max_slippage = Some(liquidity_max_slippage);
+        self
+    }
+
+    /// Sets the maximum slippage for the swap performed when providing single-sided liquidity.
+    pub fn swap_max_slippage(mut self, swap_max_slippage: Decimal) -> Self {
+        self.swap_max_slippage = Some(swap_max_slippage);
+        self
+    }
+
+    /// Sets the receiver of the LP tokens.
+    pub fn receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.receiver = Some(receiver.into());
+        self
+    }
+
+    /// Locks the LP tokens in the farm manager for the given unlocking duration, in seconds.
+    pub fn unlocking_duration(mut self, unlocking_duration: u64) -> Self {
+        self.unlocking_duration = Some(unlocking_duration);
+        self
+    }
+
+    /// Sets the position to lock the LP tokens in, if locking them in the farm manager.
+    pub fn lock_position_identifier(mut self, lock_position_identifier: impl Into<String>) -> Self {
+        self.lock_position_identifier = Some(lock_position_identifier.into());
+        self
+    }
+
+    /// Builds the message, attaching the assets to deposit.
+    pub fn build(self) -> StdResult<CosmosMsg> {
+        ensure!(
+            !self.assets.is_empty(),
+            StdError::generic_err("At least one asset must be provided")
+        );
+        ensure!(
+            self.assets.len() == 1 || self.swap_max_slippage.is_none(),
+            StdError::generic_err(
+                "The swap max slippage only applies when providing single-sided liquidity"
+            )
+        );
+        ensure!(
+            self.unlocking_duration.is_some() || self.lock_position_identifier.is_none(),
+            StdError::generic_err(
+                "The unlocking duration must be provided to lock the LP tokens in a position"
+            )
+        );
+        for asset in &self.assets {
+            ensure_non_zero(asset)?;
+        }
+
+        execute_msg(
+            &self.contract_addr,
+            &ExecuteMsg::ProvideLiquidity {
+                liquidity_max_slippage: self.liquidity_max_slippage,
+                swap_max_slippage: self.swap_max_slippage,
+                receiver: self.receiver,
+                pool_identifier: self.pool_identifier,
+                unlocking_duration: self.unlocking_duration,
+                lock_position_identifier: self.lock_position_identifier,
+            },
+            aggregate_coins(self.assets)?,
+        )
+    }
+}
+
+/// Builder for the `Swap` message. Created with [PoolManagerMsgBuilder::swap].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapBuilder {
+    contract_addr: String,
+    pool_identifier: String,
+    offer_asset: Coin,
+    ask_asset_denom: String,
+    belief_price: Option<Decimal>,
+    max_slippage: Option<Decimal>,
+    receiver: Option<String>,
+}
+
+impl SwapBuilder {
+    /// Sets the belief price of the swap.
+    pub fn belief_price(mut self, belief_price: Decimal) -> Self {
+        self.belief_price = Some(belief_price);
+        self
+    }
+
+    /// Sets the maximum slippage for the swap.
+    pub fn max_slippage(mut self, max_slippage: Decimal) -> Self {
+        self.max_slippage = Some(max_slippage);
+        self
+    }
+
+    /// Sets the receiver of the swapped tokens.
+    pub fn receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.receiver = Some(receiver.into());
+        self
+    }
+
+    /// Builds the message, attaching the offer asset.
+    pub fn build(self) -> StdResult<CosmosMsg> {
+        ensure_non_zero(&self.offer_asset)?;
+        ensure!(
+            self.offer_asset.denom != self.ask_asset_denom,
+            StdError::generic_err("Offer and ask assets must be different")
+        );
+
+        execute_msg(
+            &self.contract_addr,
+            &ExecuteMsg::Swap {
+                ask_asset_denom: self.ask_asset_denom,
+                belief_price: self.belief_price,
+                max_slippage: self.max_slippage,
+                receiver: self.receiver,
+                pool_identifier: self.pool_identifier,
+            },
+            vec![self.offer_asset],
+        )
+    }
+}
+
+/// Wraps the given [ExecuteMsg] in a [WasmMsg::Execute] to the pool manager.
+fn execute_msg(contract_addr: &str, msg: &ExecuteMsg, funds: Vec<Coin>) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(msg)?,
+        funds,
+    }))
+}
+
+/// Ensures the given coin has a non-zero amount.
+fn ensure_non_zero(coin: &Coin) -> StdResult<()> {
+    ensure!(
+        !coin.amount.is_zero(),
+        StdError::generic_err(format!("Invalid zero amount for {}", coin.denom))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, from_json, CosmosMsg, Decimal, StdError, WasmMsg};
+
+    use crate::fee::{Fee, PoolFee};
+    use crate::pool_manager::{ExecuteMsg, PoolType};
+    use crate::pool_manager_builder::PoolManagerMsgBuilder;
+
+    fn unwrap_execute(msg: CosmosMsg) -> (ExecuteMsg, Vec<cosmwasm_std::Coin>) {
+        match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                assert_eq!(contract_addr, "pool_manager");
+                (from_json(msg).unwrap(), funds)
+            }
+            _ => panic!("expected a wasm execute message"),
+        }
+    }
+
+    #[test]
+    fn create_pool_aggregates_fees() {
+        let fee = Fee {
+            share: Decimal::permille(1),
+        };
+        let msg = PoolManagerMsgBuilder::new("pool_manager")
+            .create_pool(
+                vec!["uom".to_string(), "uusdc".to_string()],
+                vec![6, 6],
+                PoolFee {
+                    protocol_fee: fee.clone(),
+                    swap_fee: fee.clone(),
+                    burn_fee: fee,
+                    extra_fees: vec![],
+                },
+                PoolType::ConstantProduct,
+            )
+            .pool_identifier("om.usdc")
+            .pool_creation_fee(coin(1_000, "uusdc"))
+            .denom_creation_fee(vec![coin(500, "uom"), coin(100, "uusdc")])
+            .build()
+            .unwrap();
+
+        let (msg, funds) = unwrap_execute(msg);
+        assert!(
+            matches!(msg, ExecuteMsg::CreatePool { pool_identifier: Some(id), .. } if id == "om.usdc")
+        );
+        assert_eq!(funds, vec![coin(500, "uom"), coin(1_100, "uusdc")]);
+    }
+
+    #[test]
+    fn provide_liquidity_single_sided() {
+        let builder = PoolManagerMsgBuilder::new("pool_manager");
+
+        let msg = builder
+            .provide_liquidity("om.usdc", vec![coin(1_000, "uom")])
+            .swap_max_slippage(Decimal::percent(1))
+            .unlocking_duration(86_400)
+            .build()
+            .unwrap();
+        let (msg, funds) = unwrap_execute(msg);
+        assert!(matches!(
+            msg,
+            ExecuteMsg::ProvideLiquidity {
+                swap_max_slippage: Some(_),
+                unlocking_duration: Some(86_400),
+                ..
+            }
+        ));
+        assert_eq!(funds, vec![coin(1_000, "uom")]);
+
+        assert_eq!(
+            builder
+                .provide_liquidity("om.usdc", vec![coin(1_000, "uom"), coin(1_000, "uusdc")])
+                .swap_max_slippage(Decimal::percent(1))
+                .build(),
+            Err(StdError::generic_err(
+                "The swap max slippage only applies when providing single-sided liquidity"
+            ))
+        );
+    }
+
+    #[test]
+    fn swap_and_withdraw() {
+        let builder = PoolManagerMsgBuilder::new("pool_manager");
+
+        let (msg, funds) = unwrap_execute(
+            builder
+                .swap("om.usdc", coin(1_000, "uom"), "uusdc")
+                .max_slippage(Decimal::percent(1))
+                .build()
+                .unwrap(),
+        );
+        assert!(
+            matches!(msg, ExecuteMsg::Swap { ask_asset_denom, .. } if ask_asset_denom == "uusdc")
+        );
+        assert_eq!(funds, vec![coin(1_000, "uom")]);
+
+        assert!(builder
+            .swap("om.usdc", coin(0, "uom"), "uusdc")
+            .build()
+            .is_err());
+        assert!(builder
+            .withdraw_liquidity("om.usdc", coin(0, "lp"))
+            .is_err());
+    }
+}
@@ -0,0 +1,769 @@
+use std::cmp::Ordering;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal256, StdError, StdResult, Uint128, Uint256};
+
+use crate::fee::PoolFee;
+use crate::pool_manager::{PoolInfo, PoolType, ReverseSimulationResponse, SimulationResponse};
+
+/// The amount of iterations to perform when calculating the Newton-Raphson approximation.
+const NEWTON_ITERATIONS: u64 = 32;
+
+/// The direction of a stableswap computation, i.e. whether the offer amount is being added to
+/// the offer pool (simulation) or the ask amount is being removed from the ask pool (reverse
+/// simulation).
+#[cw_serde]
+pub enum StableSwapDirection {
+    Simulate,
+    ReverseSimulate,
+}
+
+/// The result of computing a swap, i.e. given an offer amount, how much of the ask asset is
+/// returned and how much is taken by each fee.
+#[cw_serde]
+pub struct SwapComputation {
+    /// The return amount of the ask asset, after fees.
+    pub return_amount: Uint128,
+    /// The slippage amount of the swap.
+    pub slippage_amount: Uint128,
+    /// The swap fee amount of the swap.
+    pub swap_fee_amount: Uint128,
+    /// The protocol fee amount of the swap.
+    pub protocol_fee_amount: Uint128,
+    /// The burn fee amount of the swap.
+    pub burn_fee_amount: Uint128,
+    /// The extra fees amount of the swap.
+    pub extra_fees_amount: Uint128,
+}
+
+impl SwapComputation {
+    pub fn to_simulation_response(self) -> SimulationResponse {
+        SimulationResponse {
+            return_amount: self.return_amount,
+            slippage_amount: self.slippage_amount,
+            swap_fee_amount: self.swap_fee_amount,
+            protocol_fee_amount: self.protocol_fee_amount,
+            burn_fee_amount: self.burn_fee_amount,
+            extra_fees_amount: self.extra_fees_amount,
+        }
+    }
+}
+
+/// The result of computing a reverse swap, i.e. given an ask amount, how much of the offer asset
+/// is needed and how much is taken by each fee.
+#[cw_serde]
+pub struct OfferAmountComputation {
+    /// The amount of the offer asset needed to get the ask amount.
+    pub offer_amount: Uint128,
+    /// The slippage amount of the swap.
+    pub slippage_amount: Uint128,
+    /// The swap fee amount of the swap.
+    pub swap_fee_amount: Uint128,
+    /// The protocol fee amount of the swap.
+    pub protocol_fee_amount: Uint128,
+    /// The burn fee amount of the swap.
+    pub burn_fee_amount: Uint128,
+    /// The extra fees amount of the swap.
+    pub extra_fees_amount: Uint128,
+}
+
+impl OfferAmountComputation {
+    pub fn to_reverse_simulation_response(self) -> ReverseSimulationResponse {
+        ReverseSimulationResponse {
+            offer_amount: self.offer_amount,
+            slippage_amount: self.slippage_amount,
+            swap_fee_amount: self.swap_fee_amount,
+            protocol_fee_amount: self.protocol_fee_amount,
+            burn_fee_amount: self.burn_fee_amount,
+            extra_fees_amount: self.extra_fees_amount,
+        }
+    }
+}
+
+/// Simulates a swap on the given pool, returning the same result as the pool manager's
+/// `Simulation` query would for the given pool state.
+pub fn simulate_swap(
+    pool_info: &PoolInfo,
+    offer_asset: &Coin,
+    ask_asset_denom: &str,
+) -> StdResult<SimulationResponse> {
+    let (offer_index, ask_index) =
+        get_asset_indexes_in_pool(pool_info, &offer_asset.denom, ask_asset_denom)?;
+
+    compute_swap(
+        Uint256::from(pool_info.assets.len() as u128),
+        pool_info.assets[offer_index].amount,
+        pool_info.assets[ask_index].amount,
+        offer_asset.amount,
+        &pool_info.pool_fees,
+        &pool_info.pool_type,
+        pool_info.asset_decimals[offer_index],
+        pool_info.asset_decimals[ask_index],
+    )
+    .map(SwapComputation::to_simulation_response)
+}
+
+/// Simulates a reverse swap on the given pool, returning the same result as the pool manager's
+/// `ReverseSimulation` query would for the given pool state.
+pub fn reverse_simulate_swap(
+    pool_info: &PoolInfo,
+    ask_asset: &Coin,
+    offer_asset_denom: &str,
+) -> StdResult<ReverseSimulationResponse> {
+    let (offer_index, ask_index) =
+        get_asset_indexes_in_pool(pool_info, offer_asset_denom, &ask_asset.denom)?;
+
+    compute_offer_amount(
+        Uint256::from(pool_info.assets.len() as u128),
+        pool_info.assets[offer_index].amount,
+        pool_info.assets[ask_index].amount,
+        ask_asset.amount,
+        &pool_info.pool_fees,
+        &pool_info.pool_type,
+        pool_info.asset_decimals[offer_index],
+        pool_info.asset_decimals[ask_index],
+    )
+    .map(OfferAmountComputation::to_reverse_simulation_response)
+}
+
+/// Gets the spot price of the base asset in terms of the quote asset in the given pool, i.e. how
+/// much of the quote asset one unit of the base asset is worth, ignoring fees and slippage. Both
+/// assets are normalized by their decimals. Returns zero if the pool has no liquidity.
+pub fn spot_price(
+    pool_info: &PoolInfo,
+    base_denom: &str,
+    quote_denom: &str,
+) -> StdResult<Decimal256> {
+    let (base_index, quote_index) = get_asset_indexes_in_pool(pool_info, base_denom, quote_denom)?;
+
+    let base_precision = pool_info.asset_decimals[base_index];
+    let quote_precision = pool_info.asset_decimals[quote_index];
+    let base_pool =
+        decimal_with_precision(pool_info.assets[base_index].amount.into(), base_precision)?;
+    let quote_pool =
+        decimal_with_precision(pool_info.assets[quote_index].amount.into(), quote_precision)?;
+
+    if base_pool.is_zero() || quote_pool.is_zero() {
+        return Ok(Decimal256::zero());
+    }
+
+    match &pool_info.pool_type {
+        PoolType::ConstantProduct => decimal_div(quote_pool, base_pool),
+        PoolType::Weighted { weights } => {
+            let get_weight = |index: usize| {
+                weights
+                    .get(index)
+                    .map(|weight| Decimal256::from(*weight))
+                    .ok_or_else(|| {
+                        StdError::generic_err(format!(
+                            "Missing weight for asset {index} in pool {}",
+                            pool_info.pool_identifier
+                        ))
+                    })
+            };
+
+            // price = (quote_pool / quote_weight) / (base_pool / base_weight)
+            decimal_div(
+                quote_pool.checked_mul(get_weight(base_index)?)?,
+                base_pool.checked_mul(get_weight(quote_index)?)?,
+            )
+        }
+        PoolType::StableSwap { amp } => {
+            let n_coins = Uint256::from(pool_info.assets.len() as u128);
+            let n_coins_decimal = decimal_from_ratio(n_coins, Uint256::one())?;
+            let ann = decimal_from_ratio(
+                Uint256::from_u128((*amp).into()).checked_mul(n_coins)?,
+                Uint256::one(),
+            )?;
+
+            let d = calculate_stableswap_d(
+                n_coins,
+                base_pool,
+                quote_pool,
+                amp,
+                base_precision.max(quote_precision),
+            )?;
+
+            // d_prod = d^3 / (n^2 * base_pool * quote_pool), as in calculate_stableswap_d
+            let d_prod = decimal_div(
+                decimal_div(d.checked_mul(d)?, base_pool.checked_mul(n_coins_decimal)?)?
+                    .checked_mul(d)?,
+                quote_pool.checked_mul(n_coins_decimal)?,
+            )?;
+
+            // the price is the ratio of the partial derivatives of the invariant, i.e.
+            // (ann + d_prod / base_pool) / (ann + d_prod / quote_pool)
+            decimal_div(
+                ann.checked_add(decimal_div(d_prod, base_pool)?)?,
+                ann.checked_add(decimal_div(d_prod, quote_pool)?)?,
+            )
+        }
+        PoolType::CappedRange { .. } => Err(StdError::generic_err(format!(
+            "Spot price is not supported for {} pools",
+            pool_info.pool_type.get_label()
+        ))),
+    }
+}
+
+/// Gets the indexes of the offer and ask assets in the given pool. The indexes are valid both for
+/// `assets` and `asset_decimals`.
+pub fn get_asset_indexes_in_pool(
+    pool_info: &PoolInfo,
+    offer_asset_denom: &str,
+    ask_asset_denom: &str,
+) -> StdResult<(usize, usize)> {
+    if offer_asset_denom == ask_asset_denom {
+        return Err(StdError::generic_err(
+            "Offer and ask assets must be different",
+        ));
+    }
+
+    if pool_info.assets.len() != pool_info.asset_decimals.len() {
+        return Err(StdError::generic_err(format!(
+            "Pool {} has mismatching assets and decimals",
+            pool_info.pool_identifier
+        )));
+    }
+
+    let find_index = |denom: &str| {
+        pool_info
+            .assets
+            .iter()
+            .position(|asset| asset.denom == denom)
+            .ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "Asset {denom} not found in pool {}",
+                    pool_info.pool_identifier
+                ))
+            })
+    };
+
+    Ok((find_index(offer_asset_denom)?, find_index(ask_asset_denom)?))
+}
+
+/// Computes the swap of `offer_amount` into the ask asset, given the amounts of the offer and ask
+/// assets in the pool.
+///
+/// The fees are computed on the return amount, i.e. after slippage, and deducted from it.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_swap(
+    n_coins: Uint256,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+    pool_fees: &PoolFee,
+    pool_type: &PoolType,
+    offer_precision: u8,
+    ask_precision: u8,
+) -> StdResult<SwapComputation> {
+    let offer_pool: Uint256 = offer_pool.into();
+    let ask_pool: Uint256 = ask_pool.into();
+    let offer_amount: Uint256 = offer_amount.into();
+
+    match pool_type {
+        PoolType::ConstantProduct => {
+            // offer => ask
+            // ask_amount = (ask_pool - cp / (offer_pool + offer_amount))
+            let cp: Uint256 = offer_pool.checked_mul(ask_pool)?;
+            let return_amount: Uint256 = decimal_from_ratio(ask_pool, Uint256::one())?
+                .checked_sub(decimal_from_ratio(
+                    cp,
+                    offer_pool.checked_add(offer_amount)?,
+                )?)?
+                .to_uint_floor();
+
+            // calculate slippage, i.e. the difference between the spot price return and the actual return
+            let slippage_amount: Uint256 = offer_amount
+                .mul_floor(decimal_from_ratio(ask_pool, offer_pool)?)
+                .checked_sub(return_amount)?;
+
+            get_swap_computation(return_amount, slippage_amount, pool_fees)
+        }
+        PoolType::StableSwap { amp } => {
+            let offer_pool = decimal_with_precision(offer_pool, offer_precision)?;
+            let ask_pool = decimal_with_precision(ask_pool, ask_precision)?;
+            let offer_amount = decimal_with_precision(offer_amount, offer_precision)?;
+
+            let new_pool = calculate_stableswap_y(
+                n_coins,
+                offer_pool,
+                ask_pool,
+                offer_amount,
+                amp,
+                ask_precision,
+                StableSwapDirection::Simulate,
+            )?;
+
+            let return_amount = to_uint256_with_precision(ask_pool, ask_precision)?
+                .checked_sub(Uint256::from_uint128(new_pool))?;
+
+            // the slippage is the difference between the offer amount, normalized to the ask
+            // precision, and the return amount
+            let slippage_amount = to_uint256_with_precision(offer_amount, ask_precision)?
+                .saturating_sub(return_amount);
+
+            get_swap_computation(return_amount, slippage_amount, pool_fees)
+        }
+        PoolType::Weighted { .. } | PoolType::CappedRange { .. } => {
+            Err(unsupported_pool_type_error(pool_type))
+        }
+    }
+}
+
+/// Computes how much of the offer asset is needed to get `ask_amount` out of the pool, given the
+/// amounts of the offer and ask assets in the pool.
+///
+/// The fees are computed on the ask amount before the fees are deducted from it.
+#[allow(clippy::too_many_arguments)]
+pub fn compute_offer_amount(
+    n_coins: Uint256,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    ask_amount: Uint128,
+    pool_fees: &PoolFee,
+    pool_type: &PoolType,
+    offer_precision: u8,
+    ask_precision: u8,
+) -> StdResult<OfferAmountComputation> {
+    let offer_pool: Uint256 = offer_pool.into();
+    let ask_pool: Uint256 = ask_pool.into();
+    let ask_amount: Uint256 = ask_amount.into();
+
+    let one_minus_commission = Decimal256::one().checked_sub(pool_fees.total_share()?)?;
+    let inv_one_minus_commission = decimal_div(Decimal256::one(), one_minus_commission)?;
+
+    match pool_type {
+        PoolType::ConstantProduct => {
+            // ask => offer
+            // offer_amount = cp / (ask_pool - ask_amount / (1 - fees)) - offer_pool
+            let cp: Uint256 = offer_pool.checked_mul(ask_pool)?;
+            let before_commission_deduction: Uint256 =
+                ask_amount.mul_floor(inv_one_minus_commission);
+
+            let offer_amount: Uint256 = Uint256::one()
+                .checked_multiply_ratio(cp, ask_pool.checked_sub(before_commission_deduction)?)
+                .map_err(|e| StdError::generic_err(e.to_string()))?
+                .checked_sub(offer_pool)?;
+
+            let before_slippage_deduction: Uint256 =
+                offer_amount.mul_floor(decimal_from_ratio(ask_pool, offer_pool)?);
+            let slippage_amount =
+                before_slippage_deduction.saturating_sub(before_commission_deduction);
+
+            get_offer_amount_computation(
+                offer_amount,
+                slippage_amount,
+                before_commission_deduction,
+                pool_fees,
+            )
+        }
+        PoolType::StableSwap { amp } => {
+            let offer_pool = decimal_with_precision(offer_pool, offer_precision)?;
+            let ask_pool = decimal_with_precision(ask_pool, ask_precision)?;
+
+            let before_fees = inv_one_minus_commission
+                .checked_mul(decimal_with_precision(ask_amount, ask_precision)?)?;
+
+            let before_fees_offer = to_uint256_with_precision(before_fees, offer_precision)?;
+            let before_fees_ask = to_uint256_with_precision(before_fees, ask_precision)?;
+
+            let max_precision = offer_precision.max(ask_precision);
+
+            let new_offer_pool = calculate_stableswap_y(
+                n_coins,
+                offer_pool,
+                ask_pool,
+                before_fees,
+                amp,
+                max_precision,
+                StableSwapDirection::ReverseSimulate,
+            )?;
+
+            let offer_amount = Uint256::from_uint128(new_offer_pool)
+                .checked_sub(to_uint256_with_precision(offer_pool, max_precision)?)?;
+
+            // convert into the original offer precision
+            let offer_amount = match max_precision.cmp(&offer_precision) {
+                Ordering::Equal => offer_amount,
+                // Less can't happen, as max_precision = max(offer_precision, ask_precision)
+                Ordering::Less => offer_amount.checked_mul(Uint256::from(
+                    10u128.pow(u32::from(offer_precision - max_precision)),
+                ))?,
+                Ordering::Greater => offer_amount.checked_div(Uint256::from(
+                    10u128.pow(u32::from(max_precision - offer_precision)),
+                ))?,
+            };
+
+            let slippage_amount = offer_amount.saturating_sub(before_fees_offer);
+
+            get_offer_amount_computation(offer_amount, slippage_amount, before_fees_ask, pool_fees)
+        }
+        PoolType::Weighted { .. } | PoolType::CappedRange { .. } => {
+            Err(unsupported_pool_type_error(pool_type))
+        }
+    }
+}
+
+/// Calculates the new amount of the ask pool (or the offer pool when reverse simulating) after
+/// the swap, using the stableswap invariant.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_stableswap_y(
+    n_coins: Uint256,
+    offer_pool: Decimal256,
+    ask_pool: Decimal256,
+    offer_amount: Decimal256,
+    amp: &u64,
+    ask_precision: u8,
+    direction: StableSwapDirection,
+) -> StdResult<Uint128> {
+    let ann = Uint256::from_u128((*amp).into()).checked_mul(n_coins)?;
+
+    let d = to_uint256_with_precision(
+        calculate_stableswap_d(n_coins, offer_pool, ask_pool, amp, ask_precision)?,
+        ask_precision,
+    )?;
+
+    let pool_sum = match direction {
+        StableSwapDirection::Simulate => offer_pool.checked_add(offer_amount)?,
+        StableSwapDirection::ReverseSimulate => ask_pool.checked_sub(offer_amount)?,
+    };
+    let pool_sum = to_uint256_with_precision(pool_sum, ask_precision)?;
+
+    let c = d
+        .checked_multiply_ratio(d, pool_sum.checked_mul(n_coins)?)
+        .map_err(|e| StdError::generic_err(e.to_string()))?
+        .checked_multiply_ratio(d, ann.checked_mul(n_coins)?)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let b = pool_sum.checked_add(d.checked_div(ann)?)?;
+
+    // attempt to converge solution
+    let mut y = d;
+    for _ in 0..NEWTON_ITERATIONS {
+        let previous_y = y;
+        // y_next = (y^2 + c) / (2y + b - d)
+        y = y
+            .checked_mul(y)?
+            .checked_add(c)?
+            .checked_div(y.checked_add(y)?.checked_add(b)?.checked_sub(d)?)?;
+
+        if y.abs_diff(previous_y) <= Uint256::one() {
+            return Uint128::try_from(y).map_err(|e| StdError::generic_err(e.to_string()));
+        }
+    }
+
+    // completed Newton-Raphson iterations, but never concluded
+    Err(StdError::generic_err(
+        "Failed to converge on the stableswap invariant",
+    ))
+}
+
+/// Calculates the stableswap invariant `D` for the given pools, using the Newton-Raphson method.
+pub fn calculate_stableswap_d(
+    n_coins: Uint256,
+    offer_pool: Decimal256,
+    ask_pool: Decimal256,
+    amp: &u64,
+    precision: u8,
+) -> StdResult<Decimal256> {
+    let n_coins_decimal = decimal_from_ratio(n_coins, Uint256::one())?;
+
+    let sum_pools = offer_pool.checked_add(ask_pool)?;
+    if sum_pools.is_zero() {
+        // there was nothing to swap, return `0`.
+        return Ok(Decimal256::zero());
+    }
+
+    // ann = amp * n_coins
+    let ann = decimal_from_ratio(
+        Uint256::from_u128((*amp).into()).checked_mul(n_coins)?,
+        Uint256::one(),
+    )?;
+
+    let tolerance = decimal_with_precision(Uint256::one(), precision)?;
+
+    // perform Newton-Raphson method
+    let mut current_d = sum_pools;
+    for _ in 0..NEWTON_ITERATIONS {
+        // d_prod = d^(n+1) / (n^n * prod(pools))
+        let d_prod = [offer_pool, ask_pool].into_iter().try_fold(
+            current_d,
+            |acc, pool| -> StdResult<Decimal256> {
+                let mul_pools = pool.checked_mul(n_coins_decimal)?;
+                decimal_div(acc.checked_mul(current_d)?, mul_pools)
+            },
+        )?;
+
+        let old_d = current_d;
+        // d = (ann * sum_pools + d_prod * n) * d / ((ann - 1) * d + (n + 1) * d_prod)
+        current_d = decimal_div(
+            ann.checked_mul(sum_pools)?
+                .checked_add(d_prod.checked_mul(n_coins_decimal)?)?
+                .checked_mul(current_d)?,
+            ann.checked_sub(Decimal256::one())?
+                .checked_mul(current_d)?
+                .checked_add(
+                    n_coins_decimal
+                        .checked_add(Decimal256::one())?
+                        .checked_mul(d_prod)?,
+                )?,
+        )?;
+
+        if current_d.abs_diff(old_d) <= tolerance {
+            return Ok(current_d);
+        }
+    }
+
+    // completed Newton-Raphson iterations, but never concluded
+    Err(StdError::generic_err(
+        "Failed to converge on the stableswap invariant",
+    ))
+}
+
+/// Builds the [SwapComputation] by computing the fees on the given return amount and deducting
+/// them from it.
+fn get_swap_computation(
+    return_amount: Uint256,
+    slippage_amount: Uint256,
+    pool_fees: &PoolFee,
+) -> StdResult<SwapComputation> {
+    let fees = pool_fees.compute_fee_breakdown(return_amount)?;
+    let return_amount = return_amount.checked_sub(Uint256::from(fees.total()?))?;
+
+    Ok(SwapComputation {
+        return_amount: Uint128::try_from(return_amount)?,
+        slippage_amount: Uint128::try_from(slippage_amount)?,
+        swap_fee_amount: fees.swap_fee_amount,
+        protocol_fee_amount: fees.protocol_fee_amount,
+        burn_fee_amount: fees.burn_fee_amount,
+        extra_fees_amount: fees.extra_fees_amount()?,
+    })
+}
+
+/// Builds the [OfferAmountComputation] by computing the fees on the ask amount before the fees
+/// were deducted.
+fn get_offer_amount_computation(
+    offer_amount: Uint256,
+    slippage_amount: Uint256,
+    before_commission_deduction: Uint256,
+    pool_fees: &PoolFee,
+) -> StdResult<OfferAmountComputation> {
+    let fees = pool_fees.compute_fee_breakdown(before_commission_deduction)?;
+
+    Ok(OfferAmountComputation {
+        offer_amount: Uint128::try_from(offer_amount)?,
+        slippage_amount: Uint128::try_from(slippage_amount)?,
+        swap_fee_amount: fees.swap_fee_amount,
+        protocol_fee_amount: fees.protocol_fee_amount,
+        burn_fee_amount: fees.burn_fee_amount,
+        extra_fees_amount: fees.extra_fees_amount()?,
+    })
+}
+
+/// The error returned when simulating swaps on pool types without swap math in this crate.
+fn unsupported_pool_type_error(pool_type: &PoolType) -> StdError {
+    StdError::generic_err(format!(
+        "Swap simulation is not supported for {} pools",
+        pool_type.get_label()
+    ))
+}
+
+/// Creates a [Decimal256] from the ratio of the given numbers, erroring instead of panicking.
+fn decimal_from_ratio(numerator: Uint256, denominator: Uint256) -> StdResult<Decimal256> {
+    Decimal256::checked_from_ratio(numerator, denominator)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// Divides the given decimals, erroring instead of panicking.
+fn decimal_div(numerator: Decimal256, denominator: Decimal256) -> StdResult<Decimal256> {
+    numerator
+        .checked_div(denominator)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// Converts an amount with the given precision to a [Decimal256], i.e. 1_000_000 with precision
+/// 6 is converted to 1.
+pub(crate) fn decimal_with_precision(value: Uint256, precision: u8) -> StdResult<Decimal256> {
+    Decimal256::from_atomics(value, u32::from(precision)).map_err(|_| {
+        StdError::generic_err(format!(
+            "Decimal256 range exceeded for value {value} with precision {precision}"
+        ))
+    })
+}
+
+/// Converts a [Decimal256] to an amount with the given precision, truncating the decimals that
+/// don't fit in it.
+pub(crate) fn to_uint256_with_precision(value: Decimal256, precision: u8) -> StdResult<Uint256> {
+    let exponent = Decimal256::DECIMAL_PLACES
+        .checked_sub(u32::from(precision))
+        .ok_or_else(|| {
+            StdError::generic_err(format!(
+                "Precision {precision} exceeds the maximum of {}",
+                Decimal256::DECIMAL_PLACES
+            ))
+        })?;
+
+    Ok(value
+        .atomics()
+        .checked_div(Uint256::from(10u128.pow(exponent)))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Decimal, Decimal256, Uint128};
+
+    use crate::pool_manager::PoolType;
+    use crate::pool_math::{reverse_simulate_swap, simulate_swap, spot_price};
+    use crate::testing::{pool, pool_fees};
+
+    #[test]
+    fn constant_product_swap() {
+        let pool = pool(
+            "p.1",
+            vec![(1_000_000, "uom", 6), (1_000_000, "uusdc", 6)],
+            PoolType::ConstantProduct,
+            pool_fees(1, 2, 0),
+        );
+
+        let simulation = simulate_swap(&pool, &coin(1_000, "uom"), "uusdc").unwrap();
+
+        // 1_000_000 - 1_000_000 * 1_000_000 / 1_001_000 = 999.000999..., floored
+        // slippage = 1_000 - 999 = 1
+        // protocol fee = 0.999 -> 0, swap fee = 1.998 -> 1
+        assert_eq!(simulation.return_amount, Uint128::new(998));
+        assert_eq!(simulation.slippage_amount, Uint128::new(1));
+        assert_eq!(simulation.protocol_fee_amount, Uint128::zero());
+        assert_eq!(simulation.swap_fee_amount, Uint128::new(1));
+        assert_eq!(simulation.burn_fee_amount, Uint128::zero());
+        assert_eq!(simulation.extra_fees_amount, Uint128::zero());
+    }
+
+    #[test]
+    fn constant_product_reverse_swap_roundtrips() {
+        let pool = pool(
+            "p.1",
+            vec![(5_000_000_000, "uom", 6), (2_000_000_000, "uusdc", 6)],
+            PoolType::ConstantProduct,
+            pool_fees(1, 2, 1),
+        );
+
+        let reverse = reverse_simulate_swap(&pool, &coin(1_000_000, "uusdc"), "uom").unwrap();
+        let simulation =
+            simulate_swap(&pool, &coin(reverse.offer_amount.u128(), "uom"), "uusdc").unwrap();
+
+        assert!(simulation.return_amount >= Uint128::new(999_990));
+        assert!(simulation.return_amount <= Uint128::new(1_000_010));
+    }
+
+    #[test]
+    fn stableswap_swap_with_different_decimals() {
+        let pool = pool(
+            "p.1",
+            vec![
+                (1_000_000_000_000, "uusdc", 6),
+                (1_000_000_000_000_000_000_000_000, "ausdy", 18),
+            ],
+            PoolType::StableSwap { amp: 100 },
+            pool_fees(0, 0, 0),
+        );
+
+        let simulation = simulate_swap(&pool, &coin(1_000_000, "uusdc"), "ausdy").unwrap();
+
+        // a balanced stableswap pool should return almost 1:1, normalized to 18 decimals
+        assert!(simulation.return_amount > Uint128::new(999_000_000_000_000_000));
+        assert!(simulation.return_amount <= Uint128::new(1_000_000_000_000_000_000));
+
+        let reverse = reverse_simulate_swap(
+            &pool,
+            &coin(simulation.return_amount.u128(), "ausdy"),
+            "uusdc",
+        )
+        .unwrap();
+
+        assert!(reverse.offer_amount.abs_diff(Uint128::new(1_000_000)) <= Uint128::new(1));
+    }
+
+    #[test]
+    fn stableswap_swap_beats_constant_product() {
+        let fees = pool_fees(1, 2, 0);
+        let assets = vec![(1_000_000_000, "uusdc", 6), (1_000_000_000, "uusdt", 6)];
+        let stable_pool = pool(
+            "p.1",
+            assets.clone(),
+            PoolType::StableSwap { amp: 85 },
+            fees.clone(),
+        );
+        let xyk_pool = pool("p.1", assets, PoolType::ConstantProduct, fees);
+
+        let offer = coin(100_000_000, "uusdc");
+        let stable = simulate_swap(&stable_pool, &offer, "uusdt").unwrap();
+        let xyk = simulate_swap(&xyk_pool, &offer, "uusdt").unwrap();
+
+        assert!(stable.return_amount > xyk.return_amount);
+        assert!(stable.slippage_amount < xyk.slippage_amount);
+    }
+
+    #[test]
+    fn swap_with_invalid_assets_fails() {
+        let pool = pool(
+            "p.1",
+            vec![(1_000_000, "uom", 6), (1_000_000, "uusdc", 6)],
+            PoolType::ConstantProduct,
+            pool_fees(1, 2, 0),
+        );
+
+        assert!(simulate_swap(&pool, &coin(1_000, "uom"), "uom").is_err());
+        assert!(simulate_swap(&pool, &coin(1_000, "uatom"), "uusdc").is_err());
+        assert!(reverse_simulate_swap(&pool, &coin(1_000, "uusdc"), "uatom").is_err());
+    }
+
+    #[test]
+    fn spot_prices() {
+        let xyk_pool = pool(
+            "p.1",
+            vec![
+                (2_000_000, "uom", 6),
+                (1_000_000_000_000_000_000, "ausdy", 18),
+            ],
+            PoolType::ConstantProduct,
+            pool_fees(1, 2, 0),
+        );
+        assert_eq!(
+            spot_price(&xyk_pool, "uom", "ausdy").unwrap(),
+            Decimal256::percent(50)
+        );
+
+        let weighted_pool = pool(
+            "p.1",
+            vec![(4_000_000, "uom", 6), (1_000_000, "uusdc", 6)],
+            PoolType::Weighted {
+                weights: vec![Decimal::percent(80), Decimal::percent(20)],
+            },
+            pool_fees(1, 2, 0),
+        );
+        assert_eq!(
+            spot_price(&weighted_pool, "uom", "uusdc").unwrap(),
+            Decimal256::one()
+        );
+
+        let balanced_stable_pool = pool(
+            "p.1",
+            vec![(1_000_000_000, "uusdc", 6), (1_000_000_000, "uusdt", 6)],
+            PoolType::StableSwap { amp: 100 },
+            pool_fees(1, 2, 0),
+        );
+        assert_eq!(
+            spot_price(&balanced_stable_pool, "uusdc", "uusdt").unwrap(),
+            Decimal256::one()
+        );
+
+        let imbalanced_stable_pool = pool(
+            "p.1",
+            vec![(500_000_000, "uusdc", 6), (1_500_000_000, "uusdt", 6)],
+            PoolType::StableSwap { amp: 100 },
+            pool_fees(1, 2, 0),
+        );
+        let price = spot_price(&imbalanced_stable_pool, "uusdc", "uusdt").unwrap();
+        assert!(price > Decimal256::one() && price < Decimal256::percent(110));
+    }
+}
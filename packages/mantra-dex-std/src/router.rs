@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{coin, ensure, Coin, Decimal, StdError, StdResult, Uint128};
+
+use crate::pool_manager::{PoolInfo, SwapOperation};
+use crate::pool_math::simulate_swap;
+
+/// The maximum amount of hops a route can have, to bound the search in [find_best_routes].
+pub const MAX_ROUTE_HOPS: usize = 4;
+
+/// A route to swap an offer asset into an ask asset, found by [find_best_routes].
+#[cw_serde]
+pub struct Route {
+    /// The operations to perform, ready to be used in `ExecuteSwapOperations` or
+    /// `SimulateSwapOperations`.
+    pub operations: Vec<SwapOperation>,
+    /// The simulated return amount of the ask asset, after all the fees of every hop.
+    pub return_amount: Uint128,
+    /// The suggested `minimum_receive` for the route, given the slippage tolerance.
+    pub minimum_receive: Uint128,
+}
+
+/// Finds the best routes, up to `max_routes`, to swap the `offer_asset` into the `ask_asset_denom`
+/// using the given snapshot of pools, e.g. the pools in a `PoolsResponse`.
+///
+/// Routes are limited to `max_hops` swaps, at most [MAX_ROUTE_HOPS], never go through the same
+/// pool or denom twice, and skip pools with swaps disabled. They are ranked by the simulated return
+/// amount after all the fees, and in case of a tie, by the amount of hops.
+pub fn find_best_routes(
+    pools: &[PoolInfo],
+    offer_asset: &Coin,
+    ask_asset_denom: &str,
+    max_hops: usize,
+    max_routes: usize,
+    max_slippage: Decimal,
+) -> StdResult<Vec<Route>> {
+    ensure!(
+        max_hops > 0,
+        StdError::generic_err("The maximum amount of hops must be greater than zero")
+    );
+    ensure!(
+        max_hops <= MAX_ROUTE_HOPS,
+        StdError::generic_err(format!(
+            "The maximum amount of hops cannot be greater than {MAX_ROUTE_HOPS}"
+        ))
+    );
+    ensure!(
+        max_routes > 0,
+        StdError::generic_err("The maximum amount of routes must be greater than zero")
+    );
+    ensure!(
+        max_slippage <= Decimal::one(),
+        StdError::generic_err("The maximum slippage cannot be greater than 100%")
+    );
+    ensure!(
+        offer_asset.denom != ask_asset_denom,
+        StdError::generic_err("Offer and ask assets must be different")
+    );
+
+    // index the pools with swaps enabled by denom, so each hop only looks at the pools it can swap on
+    let mut pools_by_denom: HashMap<&str, Vec<&PoolInfo>> = HashMap::new();
+    for pool in pools.iter().filter(|pool| pool.status.swaps_enabled) {
+        for asset in &pool.assets {
+            pools_by_denom
+                .entry(asset.denom.as_str())
+                .or_default()
+                .push(pool);
+        }
+    }
+
+    let mut routes = vec![];
+    let mut path = RoutePath {
+        operations: vec![],
+        visited_denoms: vec![offer_asset.denom.clone()],
+    };
+
+    explore_routes(
+        &pools_by_denom,
+        offer_asset,
+        ask_asset_denom,
+        max_hops,
+        &mut path,
+        &mut routes,
+    );
+
+    routes.sort_by(|(a_ops, a_return), (b_ops, b_return)| {
+        b_return
+            .cmp(a_return)
+            .then_with(|| a_ops.len().cmp(&b_ops.len()))
+    });
+    routes.truncate(max_routes);
+
+    routes
+        .into_iter()
+        .map(|(operations, return_amount)| {
+            Ok(Route {
+                operations,
+                return_amount,
+                minimum_receive: get_minimum_receive(return_amount, max_slippage)?,
+            })
+        })
+        .collect()
+}
+
+/// Gets the minimum amount to receive out of a swap, given the expected return amount and the
+/// maximum slippage tolerated.
+pub fn get_minimum_receive(return_amount: Uint128, max_slippage: Decimal) -> StdResult<Uint128> {
+    let tolerance = Decimal::one()
+        .checked_sub(max_slippage)
+        .map_err(|_| StdError::generic_err("The maximum slippage cannot be greater than 100%"))?;
+
+    Ok(return_amount.mul_floor(tolerance))
+}
+
+/// The path being explored while searching for routes.
+struct RoutePath {
+    operations: Vec<SwapOperation>,
+    visited_denoms: Vec<String>,
+}
+
+/// Explores, depth first, all the routes from the current offer asset to the ask denom, pushing the
+/// ones reaching it to `routes` together with their return amount.
+fn explore_routes(
+    pools_by_denom: &HashMap<&str, Vec<&PoolInfo>>,
+    offer_asset: &Coin,
+    ask_asset_denom: &str,
+    hops_left: usize,
+    path: &mut RoutePath,
+    routes: &mut Vec<(Vec<SwapOperation>, Uint128)>,
+) {
+    if hops_left == 0 {
+        return;
+    }
+
+    let Some(pools) = pools_by_denom.get(offer_asset.denom.as_str()) else {
+        return;
+    };
+
+    for pool in pools {
+        if path
+            .operations
+            .iter()
+            .any(|op| op.get_pool_identifer() == pool.pool_identifier)
+        {
+            continue;
+        }
+
+        for token_out_denom in pool.assets.iter().map(|a| a.denom.as_str()) {
+            if path.visited_denoms.iter().any(|d| d == token_out_denom) {
+                continue;
+            }
+
+            // pools that can't be swapped on, e.g. because they have no liquidity, are skipped
+            let return_amount = match simulate_swap(pool, offer_asset, token_out_denom) {
+                Ok(simulation) if !simulation.return_amount.is_zero() => simulation.return_amount,
+                _ => continue,
+            };
+
+            path.operations.push(SwapOperation::MantraSwap {
+                token_in_denom: offer_asset.denom.clone(),
+                token_out_denom: token_out_denom.to_string(),
+                pool_identifier: pool.pool_identifier.clone(),
+            });
+
+            if token_out_denom == ask_asset_denom {
+                routes.push((path.operations.clone(), return_amount));
+            } else {
+                path.visited_denoms.push(token_out_denom.to_string());
+                explore_routes(
+                    pools_by_denom,
+                    &coin(return_amount.u128(), token_out_denom),
+                    ask_asset_denom,
+                    hops_left - 1,
+                    path,
+                    routes,
+                );
+                path.visited_denoms.pop();
+            }
+
+            path.operations.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Decimal, StdError, Uint128};
+
+    use crate::pool_manager::{PoolInfo, PoolType, SwapOperation};
+    use crate::router::{find_best_routes, get_minimum_receive, MAX_ROUTE_HOPS};
+    use crate::testing::{pool, pool_fees};
+
+    fn pools() -> Vec<PoolInfo> {
+        vec![
+            // shallow direct pool
+            pool(
+                "o.u",
+                vec![(1_000_000, "uom", 6), (1_000_000, "uusdc", 6)],
+                PoolType::ConstantProduct,
+                pool_fees(0, 3, 0),
+            ),
+            // deep two hop route
+            pool(
+                "o.a",
+                vec![(1_000_000_000, "uom", 6), (1_000_000_000, "uatom", 6)],
+                PoolType::ConstantProduct,
+                pool_fees(0, 1, 0),
+            ),
+            pool(
+                "a.u",
+                vec![(1_000_000_000, "uatom", 6), (1_000_000_000, "uusdc", 6)],
+                PoolType::ConstantProduct,
+                pool_fees(0, 1, 0),
+            ),
+        ]
+    }
+
+    #[test]
+    fn finds_best_route_across_hops() {
+        let routes = find_best_routes(
+            &pools(),
+            &coin(100_000, "uom"),
+            "uusdc",
+            3,
+            5,
+            Decimal::percent(1),
+        )
+        .unwrap();
+
+        assert_eq!(routes.len(), 2);
+        assert_eq!(
+            routes[0].operations,
+            vec![
+                SwapOperation::MantraSwap {
+                    token_in_denom: "uom".to_string(),
+                    token_out_denom: "uatom".to_string(),
+                    pool_identifier: "o.a".to_string(),
+                },
+                SwapOperation::MantraSwap {
+                    token_in_denom: "uatom".to_string(),
+                    token_out_denom: "uusdc".to_string(),
+                    pool_identifier: "a.u".to_string(),
+                },
+            ]
+        );
+        assert!(routes[0].return_amount > routes[1].return_amount);
+        assert_eq!(
+            routes[0].minimum_receive,
+            routes[0].return_amount.mul_floor(Decimal::percent(99))
+        );
+    }
+
+    #[test]
+    fn respects_max_hops_and_max_routes() {
+        let routes = find_best_routes(
+            &pools(),
+            &coin(100_000, "uom"),
+            "uusdc",
+            1,
+            5,
+            Decimal::percent(1),
+        )
+        .unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].operations.len(), 1);
+
+        let routes = find_best_routes(
+            &pools(),
+            &coin(100_000, "uom"),
+            "uusdc",
+            3,
+            1,
+            Decimal::percent(1),
+        )
+        .unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].operations.len(), 2);
+    }
+
+    #[test]
+    fn skips_pools_with_swaps_disabled() {
+        let mut pools = pools();
+        pools[1].status.swaps_enabled = false;
+
+        let routes = find_best_routes(
+            &pools,
+            &coin(100_000, "uom"),
+            "uusdc",
+            3,
+            5,
+            Decimal::percent(1),
+        )
+        .unwrap();
+
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].operations[0].get_pool_identifer(), "o.u");
+    }
+
+    #[test]
+    fn invalid_route_params() {
+        assert_eq!(
+            find_best_routes(
+                &pools(),
+                &coin(100_000, "uom"),
+                "uusdc",
+                0,
+                5,
+                Decimal::percent(1)
+            ),
+            Err(StdError::generic_err(
+                "The maximum amount of hops must be greater than zero"
+            ))
+        );
+        assert_eq!(
+            find_best_routes(
+                &pools(),
+                &coin(100_000, "uom"),
+                "uusdc",
+                MAX_ROUTE_HOPS + 1,
+                5,
+                Decimal::percent(1)
+            ),
+            Err(StdError::generic_err(
+                "The maximum amount of hops cannot be greater than 4"
+            ))
+        );
+        assert_eq!(
+            find_best_routes(
+                &pools(),
+                &coin(100_000, "uom"),
+                "uusdc",
+                3,
+                0,
+                Decimal::percent(1)
+            ),
+            Err(StdError::generic_err(
+                "The maximum amount of routes must be greater than zero"
+            ))
+        );
+        assert!(get_minimum_receive(Uint128::new(100), Decimal::percent(101)).is_err());
+        assert_eq!(
+            get_minimum_receive(Uint128::new(1_000), Decimal::percent(5)).unwrap(),
+            Uint128::new(950)
+        );
+    }
+}
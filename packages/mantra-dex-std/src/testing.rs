@@ -0,0 +1,51 @@
+//! Fixtures shared by the unit tests of the crate.
+
+use cosmwasm_std::{coin, Decimal};
+
+use crate::fee::{Fee, PoolFee};
+use crate::pool_manager::{PoolInfo, PoolStatus, PoolType};
+
+/// Pool fees with the given protocol, swap and burn fees, in permille.
+pub(crate) fn pool_fees(protocol: u64, swap: u64, burn: u64) -> PoolFee {
+    PoolFee {
+        protocol_fee: Fee {
+            share: Decimal::permille(protocol),
+        },
+        swap_fee: Fee {
+            share: Decimal::permille(swap),
+        },
+        burn_fee: Fee {
+            share: Decimal::permille(burn),
+        },
+        extra_fees: vec![],
+    }
+}
+
+/// A pool with the given `(amount, denom, decimals)` assets.
+pub(crate) fn pool(
+    identifier: &str,
+    assets: Vec<(u128, &str, u8)>,
+    pool_type: PoolType,
+    pool_fees: PoolFee,
+) -> PoolInfo {
+    PoolInfo {
+        pool_identifier: identifier.to_string(),
+        asset_denoms: assets.iter().map(|(_, d, _)| d.to_string()).collect(),
+        lp_denom: format!("factory/pool_manager/{identifier}.LP"),
+        asset_decimals: assets.iter().map(|(_, _, p)| *p).collect(),
+        assets: assets.iter().map(|(a, d, _)| coin(*a, *d)).collect(),
+        pool_type,
+        pool_fees,
+        status: PoolStatus::default(),
+    }
+}
+
+/// An uom/uusdc pool with a 0.3% swap fee and no other fees.
+pub(crate) fn om_usdc_pool(uom: u128, uusdc: u128, pool_type: PoolType) -> PoolInfo {
+    pool(
+        "om.usdc",
+        vec![(uom, "uom", 6), (uusdc, "uusdc", 6)],
+        pool_type,
+        pool_fees(0, 3, 0),
+    )
+}
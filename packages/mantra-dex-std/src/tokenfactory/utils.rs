@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use cosmwasm_std::{Deps, StdResult, Uint128};
+use cosmwasm_std::{Deps, StdError, StdResult, Uint128};
 use mantrachain_std::types::osmosis::tokenfactory::v1beta1::TokenfactoryQuerier;
 
 /// Gets the factory denom creation fee
@@ -10,20 +10,21 @@ pub fn get_factory_denom_creation_fee(deps: Deps) -> StdResult<Vec<cosmwasm_std:
 
     if let Some(denom_creation_fee) = denom_creation_params {
         // convert osmosis_std::types::cosmos::base::v1beta1::Coin to cosmwasm_std::Coin
-        let denom_creation_fee: Vec<cosmwasm_std::Coin> = denom_creation_fee
+        let denom_creation_fee = denom_creation_fee
             .denom_creation_fee
             .iter()
             .map(|coin| {
-                let amount = Uint128::from_str(&coin.amount);
-                match amount {
-                    Ok(amount) => cosmwasm_std::Coin {
-                        denom: coin.denom.clone(),
-                        amount,
-                    },
-                    Err(err) => panic!("Invalid amount: {}", err),
-                }
+                Ok(cosmwasm_std::Coin {
+                    denom: coin.denom.clone(),
+                    amount: Uint128::from_str(&coin.amount).map_err(|err| {
+                        StdError::generic_err(format!(
+                            "Invalid denom creation fee amount {}: {err}",
+                            coin.amount
+                        ))
+                    })?,
+                })
             })
-            .collect();
+            .collect::<StdResult<Vec<_>>>()?;
 
         Ok(denom_creation_fee)
     } else {
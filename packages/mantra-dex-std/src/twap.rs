@@ -0,0 +1,254 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{ensure, Decimal256, StdError, StdResult, Timestamp, Uint256};
+
+use crate::pool_manager::{PoolInfo, PoolType};
+use crate::pool_math::spot_price;
+
+/// The accumulated price of the base asset in terms of the quote asset of a pool.
+#[cw_serde]
+pub struct PriceAccumulator {
+    /// The denom of the asset being priced.
+    pub base_denom: String,
+    /// The denom of the asset the price is expressed in.
+    pub quote_denom: String,
+    /// The spot price at the last update of the accumulator.
+    pub last_spot_price: Decimal256,
+    /// The sum of every spot price multiplied by the seconds it was in effect, since the
+    /// accumulator was created.
+    pub cumulative_price: Decimal256,
+}
+
+/// Accumulates the spot prices of every pair of assets in a pool over time, so time-weighted
+/// average prices (TWAPs) can be computed between two snapshots of the accumulator.
+///
+/// The pool manager updates the accumulator with [TwapAccumulator::update] after every change to
+/// the pool's reserves, i.e. swaps, deposits and withdrawals. The previous spot price is assumed
+/// to have been in effect from the last update until the current block time, so several updates
+/// within the same block only keep the last spot price.
+#[cw_serde]
+pub struct TwapAccumulator {
+    /// The identifier of the pool the accumulator belongs to.
+    pub pool_identifier: String,
+    /// The block time of the last update, in seconds.
+    pub last_updated: u64,
+    /// The accumulated prices, for every ordered pair of assets in the pool.
+    pub prices: Vec<PriceAccumulator>,
+}
+
+impl TwapAccumulator {
+    /// Creates a new accumulator for the given pool, with the current spot prices. `CappedRange`
+    /// pools aren't supported, as they have no spot price, see [spot_price].
+    pub fn new(pool_info: &PoolInfo, block_time: &Timestamp) -> StdResult<Self> {
+        ensure!(
+            !matches!(pool_info.pool_type, PoolType::CappedRange { .. }),
+            StdError::generic_err(format!(
+                "TWAPs are not supported for {} pools",
+                pool_info.pool_type.get_label()
+            ))
+        );
+
+        let mut prices = vec![];
+
+        for base in &pool_info.asset_denoms {
+            for quote in pool_info.asset_denoms.iter().filter(|d| *d != base) {
+                prices.push(PriceAccumulator {
+                    base_denom: base.clone(),
+                    quote_denom: quote.clone(),
+                    last_spot_price: spot_price(pool_info, base, quote)?,
+                    cumulative_price: Decimal256::zero(),
+                });
+            }
+        }
+
+        Ok(TwapAccumulator {
+            pool_identifier: pool_info.pool_identifier.clone(),
+            last_updated: block_time.seconds(),
+            prices,
+        })
+    }
+
+    /// Updates the accumulator after the pool reserves have changed. The previous spot prices are
+    /// accumulated for the time elapsed since the last update, and the new spot prices are
+    /// taken from the given pool.
+    pub fn update(&mut self, pool_info: &PoolInfo, block_time: &Timestamp) -> StdResult<()> {
+        ensure!(
+            pool_info.pool_identifier == self.pool_identifier,
+            StdError::generic_err(format!(
+                "Cannot update the TWAP accumulator of pool {} with pool {}",
+                self.pool_identifier, pool_info.pool_identifier
+            ))
+        );
+
+        let current_time = block_time.seconds();
+        for price in self.prices.iter_mut() {
+            price.cumulative_price = accumulate(price, self.last_updated, current_time)?;
+            price.last_spot_price = spot_price(pool_info, &price.base_denom, &price.quote_denom)?;
+        }
+        self.last_updated = current_time;
+
+        Ok(())
+    }
+
+    /// Gets the cumulative price of the given pair at the given time, which must be at or after
+    /// the last update. The last spot price is assumed to be in effect since the last update.
+    pub fn cumulative_price_at(
+        &self,
+        base_denom: &str,
+        quote_denom: &str,
+        time: u64,
+    ) -> StdResult<Decimal256> {
+        let price = self
+            .prices
+            .iter()
+            .find(|p| p.base_denom == base_denom && p.quote_denom == quote_denom)
+            .ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "No price accumulated for {base_denom}/{quote_denom} in pool {}",
+                    self.pool_identifier
+                ))
+            })?;
+
+        accumulate(price, self.last_updated, time)
+    }
+}
+
+/// Computes the time-weighted average price of the base asset in terms of the quote asset between
+/// `start_time` and `end_time`.
+///
+/// `start` and `end` are the snapshots of the accumulator at, or right before, each time, i.e.
+/// the last snapshots with `last_updated` lower or equal to the respective time.
+pub fn compute_twap(
+    start: &TwapAccumulator,
+    end: &TwapAccumulator,
+    base_denom: &str,
+    quote_denom: &str,
+    start_time: u64,
+    end_time: u64,
+) -> StdResult<Decimal256> {
+    ensure!(
+        start.pool_identifier == end.pool_identifier,
+        StdError::generic_err("The TWAP snapshots must belong to the same pool")
+    );
+    ensure!(
+        start_time < end_time,
+        StdError::generic_err("The TWAP start time must be lower than the end time")
+    );
+
+    let start_cumulative_price = start.cumulative_price_at(base_denom, quote_denom, start_time)?;
+    let end_cumulative_price = end.cumulative_price_at(base_denom, quote_denom, end_time)?;
+
+    end_cumulative_price
+        .checked_sub(start_cumulative_price)
+        .map_err(|_| StdError::generic_err("The TWAP snapshots are not in chronological order"))?
+        .checked_div(Decimal256::from_ratio(end_time - start_time, 1u8))
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// Accumulates the last spot price of the given pair from `last_updated` until `time`.
+fn accumulate(price: &PriceAccumulator, last_updated: u64, time: u64) -> StdResult<Decimal256> {
+    let elapsed = time.checked_sub(last_updated).ok_or_else(|| {
+        StdError::generic_err(format!(
+            "Cannot accumulate prices at {time}, before the last update at {last_updated}"
+        ))
+    })?;
+
+    Ok(price.cumulative_price.checked_add(
+        price
+            .last_spot_price
+            .checked_mul(Decimal256::from_ratio(Uint256::from(elapsed), 1u8))?,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Decimal, Decimal256, StdError, Timestamp};
+
+    use crate::pool_manager::PoolType;
+    use crate::testing::om_usdc_pool;
+    use crate::twap::{compute_twap, TwapAccumulator};
+
+    #[test]
+    fn twap_of_constant_price() {
+        let start = TwapAccumulator::new(
+            &om_usdc_pool(1_000, 2_000, PoolType::ConstantProduct),
+            &Timestamp::from_seconds(100),
+        )
+        .unwrap();
+        let mut end = start.clone();
+        end.update(
+            &om_usdc_pool(1_000, 2_000, PoolType::ConstantProduct),
+            &Timestamp::from_seconds(200),
+        )
+        .unwrap();
+
+        assert_eq!(
+            compute_twap(&start, &end, "uom", "uusdc", 100, 300).unwrap(),
+            Decimal256::percent(200)
+        );
+        assert_eq!(
+            compute_twap(&start, &end, "uusdc", "uom", 150, 250).unwrap(),
+            Decimal256::percent(50)
+        );
+    }
+
+    #[test]
+    fn twap_weights_prices_by_time() {
+        let start = TwapAccumulator::new(
+            &om_usdc_pool(1_000, 1_000, PoolType::ConstantProduct),
+            &Timestamp::from_seconds(0),
+        )
+        .unwrap();
+        let mut end = start.clone();
+        // price of 1 for 300 seconds, then 3 for 100 seconds
+        end.update(
+            &om_usdc_pool(1_000, 3_000, PoolType::ConstantProduct),
+            &Timestamp::from_seconds(300),
+        )
+        .unwrap();
+
+        assert_eq!(
+            compute_twap(&start, &end, "uom", "uusdc", 0, 400).unwrap(),
+            Decimal256::percent(150)
+        );
+        assert_eq!(
+            compute_twap(&start, &end, "uom", "uusdc", 200, 400).unwrap(),
+            Decimal256::percent(200)
+        );
+    }
+
+    #[test]
+    fn invalid_twap_updates() {
+        let mut accumulator = TwapAccumulator::new(
+            &om_usdc_pool(1_000, 1_000, PoolType::ConstantProduct),
+            &Timestamp::from_seconds(100),
+        )
+        .unwrap();
+
+        assert!(accumulator
+            .update(
+                &om_usdc_pool(1_000, 1_000, PoolType::ConstantProduct),
+                &Timestamp::from_seconds(99)
+            )
+            .is_err());
+        assert!(accumulator
+            .cumulative_price_at("uom", "uatom", 100)
+            .is_err());
+        assert!(compute_twap(&accumulator, &accumulator, "uom", "uusdc", 200, 200).is_err());
+    }
+
+    #[test]
+    fn capped_range_pools_are_not_supported() {
+        let mut capped_range_pool = om_usdc_pool(1_000, 1_000, PoolType::ConstantProduct);
+        capped_range_pool.pool_type = PoolType::CappedRange {
+            lower_price: Decimal::percent(99),
+            upper_price: Decimal::percent(101),
+        };
+
+        assert_eq!(
+            TwapAccumulator::new(&capped_range_pool, &Timestamp::from_seconds(100)),
+            Err(StdError::generic_err(
+                "TWAPs are not supported for CappedRange pools"
+            ))
+        );
+    }
+}